@@ -0,0 +1,172 @@
+use std::{str::FromStr, thread, time::Duration};
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anyhow::{anyhow, bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::{json, Value};
+use solana_hash::Hash;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+#[derive(Clone)]
+pub struct Account {
+    pub owner: Pubkey,
+    pub data: Vec<u8>,
+}
+
+/// Ledger the CLI reads accounts from and sends transactions to, so commands can run against a
+/// validator over RPC and be tested against an in-memory ledger that records what they send.
+pub trait Cluster {
+    fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>>;
+
+    /// Accounts owned by `program_id` whose data starts with `discriminator`.
+    fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        discriminator: &[u8],
+    ) -> Result<Vec<(Pubkey, Account)>>;
+
+    /// Signs `instructions` with `payer`, sends them and waits for confirmation.
+    fn send(&mut self, instructions: &[Instruction], payer: &Keypair) -> Result<String>;
+}
+
+/// JSON-RPC client for a validator, such as `solana-test-validator` on localhost.
+pub struct RpcCluster {
+    url: String,
+}
+
+const CONFIRMATION_POLLS: usize = 60;
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+impl RpcCluster {
+    pub fn new(url: String) -> Self {
+        Self { url }
+    }
+
+    fn request(&self, method: &str, params: Value) -> Result<Value> {
+        let response: Value = ureq::post(&self.url)
+            .send_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": method,
+                "params": params,
+            }))
+            .with_context(|| format!("{method} request to {} failed", self.url))?
+            .into_json()?;
+
+        if let Some(error) = response.get("error") {
+            bail!("{method} failed: {error}");
+        }
+
+        Ok(response["result"].clone())
+    }
+
+    fn parse_account(value: &Value) -> Result<Account> {
+        Ok(Account {
+            owner: Pubkey::from_str(value["owner"].as_str().context("missing account owner")?)?,
+            data: STANDARD.decode(value["data"][0].as_str().context("missing account data")?)?,
+        })
+    }
+}
+
+impl Cluster for RpcCluster {
+    fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>> {
+        let result = self.request(
+            "getAccountInfo",
+            json!([pubkey.to_string(), { "encoding": "base64", "commitment": "confirmed" }]),
+        )?;
+
+        match &result["value"] {
+            Value::Null => Ok(None),
+            value => Self::parse_account(value).map(Some),
+        }
+    }
+
+    fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        discriminator: &[u8],
+    ) -> Result<Vec<(Pubkey, Account)>> {
+        let result = self.request(
+            "getProgramAccounts",
+            json!([
+                program_id.to_string(),
+                {
+                    "encoding": "base64",
+                    "commitment": "confirmed",
+                    "filters": [{
+                        "memcmp": { "offset": 0, "bytes": bs58::encode(discriminator).into_string() }
+                    }],
+                },
+            ]),
+        )?;
+
+        result
+            .as_array()
+            .context("getProgramAccounts did not return a list")?
+            .iter()
+            .map(|keyed_account| {
+                Ok((
+                    Pubkey::from_str(
+                        keyed_account["pubkey"]
+                            .as_str()
+                            .context("missing account pubkey")?,
+                    )?,
+                    Self::parse_account(&keyed_account["account"])?,
+                ))
+            })
+            .collect()
+    }
+
+    fn send(&mut self, instructions: &[Instruction], payer: &Keypair) -> Result<String> {
+        let result = self.request("getLatestBlockhash", json!([{ "commitment": "confirmed" }]))?;
+        let blockhash = Hash::from_str(
+            result["value"]["blockhash"]
+                .as_str()
+                .context("missing blockhash")?,
+        )
+        .map_err(|err| anyhow!("invalid blockhash: {err}"))?;
+
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &[payer],
+            blockhash,
+        );
+        let signature = self
+            .request(
+                "sendTransaction",
+                json!([
+                    STANDARD.encode(bincode::serialize(&transaction)?),
+                    { "encoding": "base64", "preflightCommitment": "confirmed" },
+                ]),
+            )?
+            .as_str()
+            .context("sendTransaction did not return a signature")?
+            .to_string();
+
+        for _ in 0..CONFIRMATION_POLLS {
+            let result = self.request("getSignatureStatuses", json!([[signature]]))?;
+            let status = &result["value"][0];
+
+            if !status.is_null() {
+                if !status["err"].is_null() {
+                    bail!("transaction {signature} failed: {}", status["err"]);
+                }
+
+                if matches!(
+                    status["confirmationStatus"].as_str(),
+                    Some("confirmed" | "finalized")
+                ) {
+                    return Ok(signature);
+                }
+            }
+
+            thread::sleep(CONFIRMATION_POLL_INTERVAL);
+        }
+
+        bail!("transaction {signature} was not confirmed in time")
+    }
+}
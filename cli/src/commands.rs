@@ -0,0 +1,418 @@
+use anchor_lang::{
+    prelude::{Clock, Pubkey},
+    solana_program::sysvar,
+    Discriminator,
+};
+use anchor_spl::token_2022::spl_token_2022::{self, extension::transfer_fee::TransferFee};
+use anyhow::{anyhow, Context, Result};
+use prediction_client::{
+    accounts::{decode_config, decode_market, decode_position, decode_transfer_fee},
+    instructions,
+    pda::{config_pda, market_pda, position_pda, price_samples_pda},
+    prediction::{CreateMarketArgs, InitializeConfigArgs, Market, MarketState, TradeSharesArgs},
+    pricing::quote_trade,
+    PROGRAM_ID,
+};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+
+use crate::cluster::{Account, Cluster};
+
+/// Signer and cluster shared by every subcommand.
+pub struct Session<C: Cluster> {
+    cluster: C,
+    payer: Keypair,
+}
+
+fn state_name(state: MarketState) -> &'static str {
+    match state {
+        MarketState::Initialized => "open",
+        MarketState::Passed => "passed",
+        MarketState::Failed => "failed",
+        MarketState::Undecided => "undecided",
+        MarketState::Resolved => "resolved",
+    }
+}
+
+impl<C: Cluster> Session<C> {
+    pub fn new(cluster: C, payer: Keypair) -> Self {
+        Self { cluster, payer }
+    }
+
+    fn account(&self, pubkey: &Pubkey, name: &str) -> Result<Account> {
+        self.cluster
+            .get_account(pubkey)?
+            .with_context(|| format!("{name} account {pubkey} not found"))
+    }
+
+    fn config(&self) -> Result<prediction_client::prediction::Config> {
+        let account = self.account(&config_pda(), "config")?;
+        decode_config(&account.data).map_err(|err| anyhow!("failed to decode config: {err}"))
+    }
+
+    fn market(&self, market_id: u64) -> Result<Market> {
+        let account = self.account(&market_pda(market_id), "market")?;
+        decode_market(&account.data).map_err(|err| anyhow!("failed to decode market: {err}"))
+    }
+
+    /// Transfer fee charged by a Token-2022 `mint` in the current epoch, if it has one.
+    fn transfer_fee(&self, mint: &Account) -> Result<Option<TransferFee>> {
+        if mint.owner != spl_token_2022::ID {
+            return Ok(None);
+        }
+
+        let clock: Clock = bincode::deserialize(&self.account(&sysvar::clock::ID, "clock")?.data)
+            .context("failed to decode clock")?;
+
+        decode_transfer_fee(&mint.data, clock.epoch)
+            .map_err(|err| anyhow!("failed to decode mint: {err}"))
+    }
+
+    /// Token program owning `mint`, so Token and Token-2022 mints both work.
+    fn token_program(&self, mint: &Pubkey) -> Result<Pubkey> {
+        Ok(self.account(mint, "mint")?.owner)
+    }
+
+    fn send(
+        &mut self,
+        instructions: &[anchor_lang::solana_program::instruction::Instruction],
+    ) -> Result<()> {
+        let signature = self.cluster.send(instructions, &self.payer)?;
+        println!("Signature: {signature}");
+        Ok(())
+    }
+
+    pub fn init_config(&mut self, mint: &Pubkey, args: InitializeConfigArgs) -> Result<()> {
+        let token_program = self.token_program(mint)?;
+        let ix = instructions::initialize_config(&self.payer.pubkey(), mint, &token_program, args);
+
+        self.send(&[ix])?;
+        println!("Config: {}", config_pda());
+        Ok(())
+    }
+
+    pub fn create_market(
+        &mut self,
+        price_update_v2: &Pubkey,
+        args: CreateMarketArgs,
+    ) -> Result<()> {
+        let config = self.config()?;
+        let token_program = self.token_program(&config.mint)?;
+        let ix = instructions::create_market(
+            &self.payer.pubkey(),
+            config.market_count,
+            price_update_v2,
+            &config.mint,
+            &token_program,
+            args,
+        );
+
+        self.send(&[ix])?;
+        println!(
+            "Market {}: {}",
+            config.market_count,
+            market_pda(config.market_count)
+        );
+        Ok(())
+    }
+
+    pub fn open_position(&mut self, market_id: u64) -> Result<()> {
+        let market = market_pda(market_id);
+        let ix = instructions::open_position(&self.payer.pubkey(), &market);
+
+        self.send(&[ix])?;
+        println!("Position: {}", position_pda(&self.payer.pubkey(), &market));
+        Ok(())
+    }
+
+    pub fn trade(
+        &mut self,
+        market_id: u64,
+        shares: u64,
+        is_pass: bool,
+        is_buy: bool,
+    ) -> Result<()> {
+        let config = self.config()?;
+        let market = self.market(market_id)?;
+        let mint = self.account(&config.mint, "mint")?;
+        let transfer_fee = self.transfer_fee(&mint)?;
+        let quote = quote_trade(
+            &config,
+            &market,
+            transfer_fee.as_ref(),
+            shares,
+            is_pass,
+            is_buy,
+        )
+        .map_err(|err| anyhow!("trade rejected: {err}"))?;
+
+        println!(
+            "{} {shares} {} shares for {} with fee {}",
+            if is_buy { "Buying" } else { "Selling" },
+            if is_pass { "PASS" } else { "FAIL" },
+            quote.amount,
+            quote.fee,
+        );
+        println!(
+            "Next PASS price: {}, next FAIL price: {}",
+            quote.new_pass_price, quote.new_fail_price
+        );
+
+        let authority = self.payer.pubkey();
+        let market_key = market_pda(market_id);
+        let token_program = mint.owner;
+        let mut ixs = Vec::with_capacity(2);
+
+        if is_buy
+            && self
+                .cluster
+                .get_account(&position_pda(&authority, &market_key))?
+                .is_none()
+        {
+            ixs.push(instructions::open_position(&authority, &market_key));
+        }
+
+        ixs.push(instructions::trade_shares(
+            &authority,
+            &config.authority,
+            &market_key,
+            &config.mint,
+            &token_program,
+            TradeSharesArgs {
+                shares,
+                is_pass,
+                is_buy,
+            },
+        ));
+
+        self.send(&ixs)
+    }
+
+    pub fn settle(&mut self, market_id: u64) -> Result<()> {
+        let market = self.market(market_id)?;
+        let market_key = market_pda(market_id);
+        let price_samples = (market.twap_window > 0).then(|| price_samples_pda(&market_key));
+        let ix = instructions::settle_market(
+            &self.payer.pubkey(),
+            &market_key,
+            &market.price_update_v2,
+            price_samples,
+            &market.extra_oracles[..usize::from(market.extra_oracle_count)],
+        );
+
+        self.send(&[ix])?;
+        println!(
+            "Market {market_id}: {}",
+            state_name(self.market(market_id)?.state)
+        );
+        Ok(())
+    }
+
+    pub fn claim(&mut self, market_id: u64) -> Result<()> {
+        let config = self.config()?;
+        let token_program = self.token_program(&config.mint)?;
+        let ix = instructions::claim_winnings(
+            &self.payer.pubkey(),
+            &market_pda(market_id),
+            &config.mint,
+            &token_program,
+        );
+
+        self.send(&[ix])
+    }
+
+    pub fn list_markets(&self) -> Result<()> {
+        let mut markets = self
+            .cluster
+            .get_program_accounts(&PROGRAM_ID, Market::DISCRIMINATOR)?
+            .into_iter()
+            .filter_map(|(_, account)| decode_market(&account.data).ok())
+            .collect::<Vec<_>>();
+
+        markets.sort_by_key(|market| market.id);
+
+        for market in markets {
+            let (pass_price, fail_price) = market
+                .spot_prices()
+                .map_err(|err| anyhow!("failed to price market {}: {err}", market.id))?;
+
+            println!(
+                "{:>4}  {:<10} resolves {:>11}  PASS {:>5} bps @ {pass_price}  FAIL @ {fail_price}  {}",
+                market.id,
+                state_name(market.state),
+                market.resolve_ts,
+                market.pass_probability_bps(),
+                market.title,
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn show_position(&self, market_id: u64, authority: Option<Pubkey>) -> Result<()> {
+        let authority = authority.unwrap_or_else(|| self.payer.pubkey());
+        let position_key = position_pda(&authority, &market_pda(market_id));
+        let account = self.account(&position_key, "position")?;
+        let position = decode_position(&account.data)
+            .map_err(|err| anyhow!("failed to decode position: {err}"))?;
+
+        println!("Position:       {position_key}");
+        println!("Authority:      {}", position.authority);
+        println!("Market:         {}", position.market);
+        println!(
+            "PASS shares:    {} (cost basis {})",
+            position.pass_shares, position.pass_cost_basis
+        );
+        println!(
+            "FAIL shares:    {} (cost basis {})",
+            position.fail_shares, position.fail_cost_basis
+        );
+        println!("Realized PnL:   {}", position.realized_pnl);
+        println!("Fees paid:      {}", position.total_fees_paid);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use anchor_lang::{solana_program::instruction::Instruction, AccountSerialize};
+    use prediction_client::prediction::{instruction::TradeShares, Config};
+
+    use super::*;
+
+    /// In-memory ledger recording sent instructions instead of executing them.
+    #[derive(Default)]
+    struct MemoryCluster {
+        accounts: HashMap<Pubkey, Account>,
+        sent: Vec<Vec<Instruction>>,
+    }
+
+    impl MemoryCluster {
+        fn insert<T: AccountSerialize>(&mut self, pubkey: Pubkey, owner: Pubkey, account: &T) {
+            let mut data = Vec::new();
+            account.try_serialize(&mut data).unwrap();
+            self.accounts.insert(pubkey, Account { owner, data });
+        }
+    }
+
+    impl Cluster for MemoryCluster {
+        fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>> {
+            Ok(self.accounts.get(pubkey).cloned())
+        }
+
+        fn get_program_accounts(
+            &self,
+            program_id: &Pubkey,
+            discriminator: &[u8],
+        ) -> Result<Vec<(Pubkey, Account)>> {
+            Ok(self
+                .accounts
+                .iter()
+                .filter(|(_, account)| {
+                    account.owner == *program_id && account.data.starts_with(discriminator)
+                })
+                .map(|(pubkey, account)| (*pubkey, account.clone()))
+                .collect())
+        }
+
+        fn send(&mut self, instructions: &[Instruction], _payer: &Keypair) -> Result<String> {
+            self.sent.push(instructions.to_vec());
+            Ok(String::from("signature"))
+        }
+    }
+
+    const TOKEN_PROGRAM: Pubkey = Pubkey::new_from_array([7; 32]);
+
+    fn session_with_market(twap_window: i64) -> Session<MemoryCluster> {
+        let mint = Pubkey::new_unique();
+        let mut cluster = MemoryCluster::default();
+
+        cluster.insert(
+            config_pda(),
+            PROGRAM_ID,
+            &Config {
+                version: 1,
+                authority: Pubkey::new_unique(),
+                fee_bps: 10,
+                mint,
+                share_price: 1_000_000,
+                min_market_duration: 0,
+                max_market_duration: 0,
+                market_count: 1,
+                bump: 255,
+                series_count: 0,
+                template_count: 0,
+                proposal_bond: 0,
+                dispute_window: 0,
+                reserved: [0; 72],
+            },
+        );
+        cluster.insert(
+            market_pda(0),
+            PROGRAM_ID,
+            &Market {
+                pass_shares: 10,
+                fail_shares: 10,
+                twap_window,
+                ..Market::new(
+                    0,
+                    0,
+                    Pubkey::new_unique(),
+                    150.0,
+                    1_000_000,
+                    255,
+                    String::from("SOL above 150"),
+                )
+            },
+        );
+        cluster.accounts.insert(
+            mint,
+            Account {
+                owner: TOKEN_PROGRAM,
+                data: Vec::new(),
+            },
+        );
+
+        Session::new(cluster, Keypair::new())
+    }
+
+    #[test]
+    fn buy_opens_missing_position_before_trading() {
+        let mut session = session_with_market(0);
+
+        session.trade(0, 5, true, true).unwrap();
+
+        let ixs = &session.cluster.sent[0];
+        assert_eq!(ixs.len(), 2);
+        assert_eq!(
+            ixs[0].accounts[2].pubkey,
+            position_pda(&session.payer.pubkey(), &market_pda(0))
+        );
+        assert_eq!(ixs[1].accounts[10].pubkey, TOKEN_PROGRAM);
+    }
+
+    #[test]
+    fn sell_does_not_open_position() {
+        let mut session = session_with_market(0);
+
+        session.trade(0, 5, true, false).unwrap();
+
+        let ixs = &session.cluster.sent[0];
+        assert_eq!(ixs.len(), 1);
+        assert_eq!(ixs[0].data[..8], *TradeShares::DISCRIMINATOR);
+    }
+
+    #[test]
+    fn settle_passes_price_samples_for_twap_markets() {
+        let mut session = session_with_market(3_600);
+
+        session.settle(0).unwrap();
+
+        assert_eq!(
+            session.cluster.sent[0][0].accounts[4].pubkey,
+            price_samples_pda(&market_pda(0))
+        );
+    }
+}
@@ -0,0 +1,171 @@
+//! Admin and trader CLI for the prediction program, built on `prediction-client`.
+
+mod cluster;
+mod commands;
+
+use std::path::PathBuf;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use prediction_client::prediction::{
+    DEFAULT_DISPUTE_WINDOW, DEFAULT_MAX_MARKET_DURATION, DEFAULT_MIN_MARKET_DURATION,
+};
+use solana_keypair::read_keypair_file;
+
+use crate::{cluster::RpcCluster, commands::Session};
+
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    /// JSON-RPC endpoint of the cluster
+    #[arg(
+        long,
+        global = true,
+        env = "PREDICTION_RPC_URL",
+        default_value = "http://127.0.0.1:8899"
+    )]
+    url: String,
+    /// Keypair signing and paying for transactions, defaults to the Solana CLI keypair
+    #[arg(long, global = true, env = "PREDICTION_KEYPAIR")]
+    keypair: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Initialize the program config with the signer as authority
+    InitConfig {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        fee_bps: u16,
+        /// Shortest allowed time between market creation and resolution, in seconds
+        #[arg(long, default_value_t = DEFAULT_MIN_MARKET_DURATION)]
+        min_market_duration: i64,
+        /// Longest allowed time between market creation and resolution, in seconds
+        #[arg(long, default_value_t = DEFAULT_MAX_MARKET_DURATION)]
+        max_market_duration: i64,
+        /// Bond posted to propose or dispute an outcome, in base units of the mint
+        #[arg(long)]
+        proposal_bond: u64,
+        /// Time a proposed outcome can be disputed for, in seconds
+        #[arg(long, default_value_t = DEFAULT_DISPUTE_WINDOW)]
+        dispute_window: i64,
+    },
+    /// Create a binary market against a Pyth or Switchboard feed
+    CreateMarket {
+        #[arg(long)]
+        price_update_v2: Pubkey,
+        /// Unix timestamp after which the market can be settled
+        #[arg(long)]
+        resolve_ts: i64,
+        #[arg(long)]
+        target_price: f64,
+        #[arg(long)]
+        title: String,
+    },
+    /// Open a position on a market for the signer
+    OpenPosition { market_id: u64 },
+    /// Buy shares, opening a position first if needed
+    Buy(TradeArgs),
+    /// Sell shares back to the market
+    Sell(TradeArgs),
+    /// Settle a market past its resolution time
+    Settle { market_id: u64 },
+    /// Claim winnings from a settled market
+    Claim { market_id: u64 },
+    /// List all markets with their state and spot prices
+    ListMarkets,
+    /// Show a position, defaulting to the signer's
+    ShowPosition {
+        market_id: u64,
+        #[arg(long)]
+        authority: Option<Pubkey>,
+    },
+}
+
+#[derive(Args)]
+struct TradeArgs {
+    market_id: u64,
+    #[arg(value_enum)]
+    side: Side,
+    shares: u64,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Side {
+    Pass,
+    Fail,
+}
+
+fn default_keypair_path() -> Result<PathBuf> {
+    let home = std::env::var_os("HOME").ok_or_else(|| anyhow!("HOME is not set"))?;
+    Ok(PathBuf::from(home).join(".config/solana/id.json"))
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let keypair_path = match cli.keypair {
+        Some(path) => path,
+        None => default_keypair_path()?,
+    };
+    let payer = read_keypair_file(&keypair_path)
+        .map_err(|err| anyhow!("failed to read keypair {}: {err}", keypair_path.display()))?;
+    let mut session = Session::new(RpcCluster::new(cli.url), payer);
+
+    match cli.command {
+        Command::InitConfig {
+            mint,
+            fee_bps,
+            min_market_duration,
+            max_market_duration,
+            proposal_bond,
+            dispute_window,
+        } => session.init_config(
+            &mint,
+            prediction_client::prediction::InitializeConfigArgs {
+                fee_bps,
+                min_market_duration,
+                max_market_duration,
+                proposal_bond,
+                dispute_window,
+            },
+        ),
+        Command::CreateMarket {
+            price_update_v2,
+            resolve_ts,
+            target_price,
+            title,
+        } => session.create_market(
+            &price_update_v2,
+            prediction_client::prediction::CreateMarketArgs {
+                resolve_ts,
+                target_price,
+                title,
+            },
+        ),
+        Command::OpenPosition { market_id } => session.open_position(market_id),
+        Command::Buy(args) => session.trade(
+            args.market_id,
+            args.shares,
+            matches!(args.side, Side::Pass),
+            true,
+        ),
+        Command::Sell(args) => session.trade(
+            args.market_id,
+            args.shares,
+            matches!(args.side, Side::Pass),
+            false,
+        ),
+        Command::Settle { market_id } => session.settle(market_id),
+        Command::Claim { market_id } => session.claim(market_id),
+        Command::ListMarkets => session.list_markets(),
+        Command::ShowPosition {
+            market_id,
+            authority,
+        } => session.show_position(market_id, authority),
+    }
+}
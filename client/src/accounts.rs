@@ -0,0 +1,36 @@
+use anchor_lang::{AccountDeserialize, Result};
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{
+        transfer_fee::{TransferFee, TransferFeeConfig},
+        BaseStateWithExtensions, StateWithExtensions,
+    },
+    state::Mint,
+};
+use prediction::{Config, Market, Position};
+
+/// Decodes account data fetched over RPC, checking its discriminator.
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+pub fn decode_config(data: &[u8]) -> Result<Config> {
+    decode(data)
+}
+
+pub fn decode_market(data: &[u8]) -> Result<Market> {
+    decode(data)
+}
+
+pub fn decode_position(data: &[u8]) -> Result<Position> {
+    decode(data)
+}
+
+/// Transfer fee a trading mint charges in `epoch`, or `None` if it has no transfer fee.
+pub fn decode_transfer_fee(data: &[u8], epoch: u64) -> Result<Option<TransferFee>> {
+    let mint = StateWithExtensions::<Mint>::unpack(data)?;
+
+    Ok(mint
+        .get_extension::<TransferFeeConfig>()
+        .ok()
+        .map(|transfer_fee_config| *transfer_fee_config.get_epoch_fee(epoch)))
+}
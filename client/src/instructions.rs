@@ -0,0 +1,224 @@
+//! Instruction builders for the core market lifecycle. Accounts derived from the program's seeds
+//! are filled in, so callers only pass the keys that cannot be derived.
+
+use anchor_lang::{
+    prelude::{AccountMeta, Pubkey},
+    solana_program::instruction::Instruction,
+    system_program, InstructionData, ToAccountMetas,
+};
+use anchor_spl::associated_token;
+use prediction::{
+    accounts, instruction, CreateMarketArgs, InitializeConfigArgs, TradeSharesArgs, ID,
+};
+
+use crate::pda::{config_pda, market_pda, position_pda, token_account};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+pub fn initialize_config(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    args: InitializeConfigArgs,
+) -> Instruction {
+    let config = config_pda();
+
+    build(
+        accounts::InitializeConfig {
+            authority: *authority,
+            config,
+            mint: *mint,
+            config_authority_token_account: token_account(&config, mint, token_program),
+            system_program: system_program::ID,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::InitializeConfig { args },
+    )
+}
+
+/// Creates market `id`, which must be the current `market_count` in config.
+pub fn create_market(
+    authority: &Pubkey,
+    id: u64,
+    price_update_v2: &Pubkey,
+    trading_mint: &Pubkey,
+    token_program: &Pubkey,
+    args: CreateMarketArgs,
+) -> Instruction {
+    let market = market_pda(id);
+
+    build(
+        accounts::CreateMarket {
+            authority: *authority,
+            config: config_pda(),
+            market,
+            price_update_v2: *price_update_v2,
+            trading_mint: *trading_mint,
+            market_token_account: token_account(&market, trading_mint, token_program),
+            system_program: system_program::ID,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::CreateMarket { args },
+    )
+}
+
+pub fn open_position(authority: &Pubkey, market: &Pubkey) -> Instruction {
+    build(
+        accounts::OpenPosition {
+            authority: *authority,
+            market: *market,
+            position: position_pda(authority, market),
+            system_program: system_program::ID,
+        },
+        instruction::OpenPosition {},
+    )
+}
+
+pub fn trade_shares(
+    authority: &Pubkey,
+    config_authority: &Pubkey,
+    market: &Pubkey,
+    trading_mint: &Pubkey,
+    token_program: &Pubkey,
+    args: TradeSharesArgs,
+) -> Instruction {
+    let config = config_pda();
+
+    build(
+        accounts::TradeShares {
+            authority: *authority,
+            config_authority: *config_authority,
+            config,
+            market: *market,
+            position: position_pda(authority, market),
+            trading_mint: *trading_mint,
+            authority_token_account: token_account(authority, trading_mint, token_program),
+            market_token_account: token_account(market, trading_mint, token_program),
+            config_authority_token_account: token_account(&config, trading_mint, token_program),
+            system_program: system_program::ID,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::TradeShares { args },
+    )
+}
+
+/// Settles `market`, passing `price_samples` for markets settled against a TWAP and any
+/// additional oracles in the order they were set on the market.
+pub fn settle_market(
+    authority: &Pubkey,
+    market: &Pubkey,
+    price_update_v2: &Pubkey,
+    price_samples: Option<Pubkey>,
+    extra_oracles: &[Pubkey],
+) -> Instruction {
+    let mut ix = build(
+        accounts::SettleMarket {
+            authority: *authority,
+            config: config_pda(),
+            market: *market,
+            price_update_v2: *price_update_v2,
+            price_samples,
+        },
+        instruction::SettleMarket {},
+    );
+
+    ix.accounts.extend(
+        extra_oracles
+            .iter()
+            .map(|oracle| AccountMeta::new_readonly(*oracle, false)),
+    );
+
+    ix
+}
+
+pub fn claim_winnings(
+    authority: &Pubkey,
+    market: &Pubkey,
+    trading_mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ClaimWinnings {
+            authority: *authority,
+            market: *market,
+            position: position_pda(authority, market),
+            trading_mint: *trading_mint,
+            authority_token_account: token_account(authority, trading_mint, token_program),
+            market_token_account: token_account(market, trading_mint, token_program),
+            system_program: system_program::ID,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::ClaimWinnings {},
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trade_shares_derives_position_and_token_accounts() {
+        let authority = Pubkey::new_unique();
+        let config_authority = Pubkey::new_unique();
+        let market = market_pda(0);
+        let mint = Pubkey::new_unique();
+        let token_program = anchor_spl::token::ID;
+
+        let ix = trade_shares(
+            &authority,
+            &config_authority,
+            &market,
+            &mint,
+            &token_program,
+            TradeSharesArgs {
+                shares: 1,
+                is_pass: true,
+                is_buy: true,
+            },
+        );
+
+        let keys = ix
+            .accounts
+            .iter()
+            .map(|meta| meta.pubkey)
+            .collect::<Vec<_>>();
+
+        assert_eq!(ix.program_id, ID);
+        assert!(ix.accounts[0].is_signer);
+        assert_eq!(keys[4], position_pda(&authority, &market));
+        assert_eq!(keys[7], token_account(&market, &mint, &token_program));
+        assert_eq!(keys[8], token_account(&config_pda(), &mint, &token_program));
+    }
+
+    #[test]
+    fn settle_market_appends_extra_oracles() {
+        let market = market_pda(0);
+        let oracles = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let ix = settle_market(
+            &Pubkey::new_unique(),
+            &market,
+            &Pubkey::new_unique(),
+            None,
+            &oracles,
+        );
+
+        // an omitted optional account is passed as the program id
+        assert_eq!(ix.accounts[4].pubkey, ID);
+        assert_eq!(ix.accounts[5..].len(), oracles.len());
+        assert!(ix.accounts[5..]
+            .iter()
+            .zip(oracles)
+            .all(|(meta, oracle)| meta.pubkey == oracle && !meta.is_writable));
+    }
+}
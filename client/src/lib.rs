@@ -0,0 +1,9 @@
+//! Off-chain client for the prediction program: PDA derivation, instruction builders, account
+//! decoders and the pricing curve shared with the program.
+
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+pub mod pricing;
+
+pub use prediction::{self, ID as PROGRAM_ID};
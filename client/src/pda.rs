@@ -0,0 +1,24 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use prediction::{CONFIG_SEED, ID, MARKET_SEED, POSITION_SEED, PRICE_SAMPLES_SEED};
+
+pub fn config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[CONFIG_SEED], &ID).0
+}
+
+pub fn market_pda(id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[MARKET_SEED, id.to_le_bytes().as_ref()], &ID).0
+}
+
+pub fn position_pda(authority: &Pubkey, market: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[POSITION_SEED, authority.as_ref(), market.as_ref()], &ID).0
+}
+
+pub fn price_samples_pda(market: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[PRICE_SAMPLES_SEED, market.as_ref()], &ID).0
+}
+
+/// Associated token account of `owner` for `mint`, including those owned by config and markets.
+pub fn token_account(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, token_program)
+}
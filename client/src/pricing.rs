@@ -0,0 +1,132 @@
+//! Pricing runs the program's own curve, so quotes match what `trade_shares` charges.
+
+use anchor_lang::{error, Result};
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFee;
+use prediction::{error::PredictionError, Config, Market};
+
+pub use prediction::utils::{calculate_outcome_price, calculate_price};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quote {
+    pub fee: u64,
+    /// Net price paid for a buy including any transfer fee, or received for a sale before it
+    pub amount: u64,
+    /// Price of the next PASS share after the trade, excluding fees
+    pub new_pass_price: u64,
+    /// Price of the next FAIL share after the trade, excluding fees
+    pub new_fail_price: u64,
+}
+
+/// Quotes a PASS or FAIL trade against decoded `config` and `market` accounts, mirroring the
+/// `quote_trade` instruction without a round trip to the cluster. `transfer_fee` is the trading
+/// mint's current transfer fee, see `accounts::decode_transfer_fee`.
+pub fn quote_trade(
+    config: &Config,
+    market: &Market,
+    transfer_fee: Option<&TransferFee>,
+    shares: u64,
+    is_pass: bool,
+    is_buy: bool,
+) -> Result<Quote> {
+    let mut market = market.clone();
+    let fee_bps = market.trade_fee_bps(config.fee_bps);
+    let (fee, mut amount) = market.trade_shares(shares, is_pass, is_buy, fee_bps)?;
+
+    // buyers cover any transfer fee, as in `trade_shares`
+    if let (true, Some(transfer_fee)) = (is_buy, transfer_fee) {
+        amount = transfer_fee
+            .calculate_inverse_fee(amount)
+            .and_then(|inverse_fee| amount.checked_add(inverse_fee))
+            .ok_or(error!(PredictionError::MathOverflow))?;
+    }
+    let (new_pass_price, new_fail_price) = market.spot_prices()?;
+
+    Ok(Quote {
+        fee,
+        amount,
+        new_pass_price,
+        new_fail_price,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::prelude::Pubkey;
+
+    use super::*;
+
+    fn config() -> Config {
+        Config {
+            version: 1,
+            authority: Pubkey::default(),
+            fee_bps: 10,
+            mint: Pubkey::default(),
+            share_price: 1_000_000,
+            min_market_duration: 0,
+            max_market_duration: 0,
+            market_count: 1,
+            bump: 255,
+            series_count: 0,
+            template_count: 0,
+            proposal_bond: 0,
+            dispute_window: 0,
+            reserved: [0; 72],
+        }
+    }
+
+    fn market() -> Market {
+        Market {
+            pass_shares: 10,
+            fail_shares: 5,
+            ..Market::new(
+                0,
+                0,
+                Pubkey::default(),
+                150.0,
+                1_000_000,
+                255,
+                String::new(),
+            )
+        }
+    }
+
+    #[test]
+    fn quote_leaves_market_untouched_and_prices_next_shares() {
+        let market = market();
+
+        let quote = quote_trade(&config(), &market, None, 5, false, true).unwrap();
+        let (fee, amount) = calculate_price(5, 10, 10, false, 10, 1_000_000).unwrap();
+
+        assert_eq!((quote.fee, quote.amount), (fee, amount));
+        assert_eq!(market.fail_shares, 5);
+        assert_eq!(quote.new_fail_price, quote.new_pass_price);
+    }
+
+    #[test]
+    fn buy_quote_covers_transfer_fee() {
+        let transfer_fee = TransferFee {
+            epoch: 0.into(),
+            maximum_fee: u64::MAX.into(),
+            transfer_fee_basis_points: 100.into(),
+        };
+
+        let buy = quote_trade(&config(), &market(), None, 5, false, true).unwrap();
+        let buy_with_fee =
+            quote_trade(&config(), &market(), Some(&transfer_fee), 5, false, true).unwrap();
+
+        // the market receives the full price once the mint withholds its fee
+        assert!(buy_with_fee.amount > buy.amount);
+        assert_eq!(
+            buy_with_fee.amount - transfer_fee.calculate_fee(buy_with_fee.amount).unwrap(),
+            buy.amount
+        );
+        assert_eq!(buy_with_fee.fee, buy.fee);
+
+        let sale = quote_trade(&config(), &market(), Some(&transfer_fee), 5, true, false).unwrap();
+
+        assert_eq!(
+            sale,
+            quote_trade(&config(), &market(), None, 5, true, false).unwrap()
+        );
+    }
+}
@@ -4,6 +4,31 @@ use anchor_lang::prelude::*;
 pub const CONFIG_SEED: &[u8] = b"config";
 pub const MARKET_SEED: &[u8] = b"market";
 pub const POSITION_SEED: &[u8] = b"position";
+pub const MARKET_METADATA_SEED: &[u8] = b"market_metadata";
+pub const MARKET_SERIES_SEED: &[u8] = b"market_series";
+pub const MARKET_TEMPLATE_SEED: &[u8] = b"market_template";
+pub const RESOLUTION_SEED: &[u8] = b"resolution";
+pub const PRICE_SAMPLES_SEED: &[u8] = b"price_samples";
 pub const ONE_IN_BASIS_POINTS: u16 = 10_000;
 pub const RESOLVE_TS_WINDOW: i64 = 15 * 60; // 15 minutes
-pub const BASE_SHARE_PRICE: u32 = 1_000_000; // 1 USDC
+pub const MAX_TRADING_MINT_DECIMALS: u8 = 9;
+pub const MAX_TITLE_LEN: usize = 128;
+pub const DEFAULT_MIN_MARKET_DURATION: i64 = 5 * 60; // 5 minutes
+pub const DEFAULT_MAX_MARKET_DURATION: i64 = 365 * 24 * 60 * 60; // 1 year
+pub const DEFAULT_DISPUTE_WINDOW: i64 = 2 * 60 * 60; // 2 hours
+pub const MAX_DESCRIPTION_LEN: usize = 1_000;
+pub const MAX_TAGS: usize = 5;
+pub const MAX_TAG_LEN: usize = 32;
+pub const MAX_URI_LEN: usize = 200;
+pub const MAX_OUTCOMES: usize = 8;
+pub const MAX_EXTRA_ORACLES: usize = 2;
+pub const MAX_PRICE_SAMPLES: usize = 32;
+pub const MAX_SERIES_MARKETS: usize = 10;
+pub const CONFIG_VERSION: u8 = 2;
+pub const MARKET_VERSION: u8 = 2;
+pub const POSITION_VERSION: u8 = 1;
+pub const MARKET_METADATA_VERSION: u8 = 1;
+pub const MARKET_SERIES_VERSION: u8 = 1;
+pub const MARKET_TEMPLATE_VERSION: u8 = 1;
+pub const RESOLUTION_VERSION: u8 = 1;
+pub const PRICE_SAMPLES_VERSION: u8 = 1;
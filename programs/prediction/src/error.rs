@@ -26,9 +26,104 @@ pub enum PredictionError {
     MarketCannotResolve,
     #[msg("Market is already settled")]
     MarketAlreadySettled,
-    #[msg("Market state must be passed or failed")]
+    #[msg("Market state must be passed, failed or resolved")]
     MarketNotSettled,
     #[msg("Position has no winning shares in market")]
     NoClaimableWinnings,
-    CustomError,
+    #[msg("Mint decimals exceed the maximum supported")]
+    InvalidMintDecimals,
+    #[msg("Share price does not match the one derived from mint decimals")]
+    InvalidSharePrice,
+    #[msg("Mint has an extension that is not supported")]
+    UnsupportedMintExtension,
+    #[msg("Account is already on the latest layout")]
+    AccountAlreadyMigrated,
+    #[msg("Account must be migrated to the latest layout first")]
+    AccountNotMigrated,
+    #[msg("Traded markets must be migrated with their token account")]
+    MissingMarketTokenAccount,
+    #[msg("Min market duration must be positive and at most max market duration")]
+    InvalidMarketDuration,
+    #[msg("Resolve timestamp must be in the future")]
+    ResolveTsInPast,
+    #[msg("Market duration is shorter than the minimum in config")]
+    MarketDurationTooShort,
+    #[msg("Market duration is longer than the maximum in config")]
+    MarketDurationTooLong,
+    #[msg("Title must not be empty")]
+    EmptyTitle,
+    #[msg("Title exceeds the maximum length")]
+    TitleTooLong,
+    #[msg("Target price must be a positive finite number")]
+    InvalidTargetPrice,
+    #[msg("Description exceeds the maximum length")]
+    DescriptionTooLong,
+    #[msg("Too many tags")]
+    TooManyTags,
+    #[msg("Tag is empty or exceeds the maximum length")]
+    InvalidTag,
+    #[msg("URI exceeds the maximum length")]
+    UriTooLong,
+    #[msg("Market has already been traded")]
+    MarketAlreadyTraded,
+    #[msg("Series must have between one and the maximum number of strikes")]
+    InvalidSeriesLength,
+    #[msg("Remaining accounts do not match the markets in series")]
+    InvalidSeriesAccounts,
+    #[msg("Strike offset must be greater than -10000 bps")]
+    InvalidStrikeOffset,
+    #[msg("Previous market does not match the last one spawned from template")]
+    InvalidPreviousMarket,
+    #[msg("Previous market spawned from template has not settled")]
+    PreviousMarketNotSettled,
+    #[msg("Instruction does not support this market kind")]
+    InvalidMarketKind,
+    #[msg("Outcome bounds must be finite, positive and strictly increasing")]
+    InvalidOutcomeBounds,
+    #[msg("Outcome does not exist in market")]
+    InvalidOutcome,
+    #[msg("Market is not settled through this instruction")]
+    InvalidResolutionKind,
+    #[msg("Resolver does not match market resolver")]
+    InvalidResolver,
+    #[msg("Proposal bond must be greater than zero")]
+    InvalidProposalBond,
+    #[msg("Dispute window must be greater than zero")]
+    InvalidDisputeWindow,
+    #[msg("Market cannot be resolved optimistically")]
+    MarketNotProposable,
+    #[msg("Resolution is not in the required state")]
+    InvalidResolutionState,
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    #[msg("Dispute window is still open")]
+    DisputeWindowOpen,
+    #[msg("Disputed resolution requires an outcome from the config authority")]
+    MissingDisputeOutcome,
+    #[msg("Token account does not belong to the resolution party")]
+    InvalidBondAccount,
+    #[msg("Oracle price is older than the maximum age")]
+    StalePrice,
+    #[msg("Market can be backed by at most two additional distinct oracles")]
+    InvalidOracleCount,
+    #[msg("Oracle tolerance must be between 0 and 10000 bps")]
+    InvalidOracleTolerance,
+    #[msg("Oracle prices diverge beyond the market tolerance")]
+    OraclePriceDivergence,
+    #[msg("TWAP window must be positive and end no later than the resolve timestamp")]
+    InvalidTwapWindow,
+    #[msg("Price samples can only be recorded within the TWAP window")]
+    OutsideSamplingWindow,
+    #[msg("Price sample recorded too soon after the previous one")]
+    PriceSampleTooSoon,
+    #[msg("Price samples do not match the market")]
+    InvalidPriceSamples,
+    #[msg("Market token account holds less than the collateral owed to shareholders")]
+    InsolventMarket,
+    #[msg("Arithmetic overflowed")]
+    MathOverflow,
+    #[msg("Division by zero")]
+    DivisionByZero,
+    #[msg("Price update does not match the requested feed id")]
+    FeedIdMismatch,
 }
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+/// Emitted when winnings are claimed, since the position holding the totals is closed.
+#[event]
+pub struct WinningsClaimed {
+    pub authority: Pubkey,
+    pub market: Pubkey,
+    pub amount: u64,
+    /// Realized PnL of the position over its lifetime, including the claim
+    pub realized_pnl: i64,
+    pub total_fees_paid: u64,
+}
@@ -6,8 +6,11 @@ use anchor_spl::{
 };
 
 use crate::{
-    error::PredictionError, imprecise_number, market_signer, precise_number, Market, MarketState,
-    Position, MARKET_SEED,
+    error::PredictionError,
+    imprecise_number, market_signer, precise_number,
+    utils::{get_realized_pnl, to_u64},
+    Market, MarketKind, MarketState, Position, WinningsClaimed, MARKET_SEED, MARKET_VERSION,
+    ONE_IN_BASIS_POINTS,
 };
 
 #[derive(Accounts)]
@@ -15,8 +18,13 @@ pub struct ClaimWinnings<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     #[account(
-        seeds = [MARKET_SEED, market.price_update_v2.key().as_ref(), market.resolve_ts.to_le_bytes().as_ref()],
+        seeds = [
+            MARKET_SEED,
+            market.address_seeds()[0].as_ref(),
+            market.address_seeds()[1].as_ref(),
+        ],
         bump = market.bump,
+        constraint = market.version == MARKET_VERSION @ PredictionError::AccountNotMigrated,
     )]
     pub market: Account<'info, Market>,
     #[account(
@@ -52,48 +60,89 @@ impl ClaimWinnings<'_> {
         let market = &ctx.accounts.market;
 
         require!(
-            ctx.accounts.market.state == MarketState::Passed
-                || ctx.accounts.market.state == MarketState::Failed,
+            matches!(
+                market.state,
+                MarketState::Passed | MarketState::Failed | MarketState::Resolved
+            ),
             PredictionError::MarketNotSettled
         );
 
         let position = &ctx.accounts.position;
+        // paying out of the collateral at settlement rather than the remaining balance keeps the
+        // payout per share the same for every claimant
+        let pot = market.total_collateral;
 
-        let (winning_position_shares, winning_market_shares) =
-            if market.state == MarketState::Passed {
-                (position.pass_shares, market.pass_shares)
-            } else {
-                (position.fail_shares, market.fail_shares)
+        let amount = if market.kind == MarketKind::Scalar {
+            // LONG and SHORT split the pot by where the settled price fell between the bounds
+            let long_pot = to_u64(
+                (pot as u128)
+                    .checked_mul(market.scalar_long_bps().into())
+                    .ok_or(PredictionError::MathOverflow)?
+                    / u128::from(ONE_IN_BASIS_POINTS),
+            )?;
+            let short_pot = pot
+                .checked_sub(long_pot)
+                .ok_or(PredictionError::MathOverflow)?;
+
+            let amount = get_pot_share(long_pot, position.pass_shares, market.pass_shares)?
+                .checked_add(get_pot_share(
+                    short_pot,
+                    position.fail_shares,
+                    market.fail_shares,
+                )?)
+                .ok_or(PredictionError::MathOverflow)?;
+
+            require!(amount > 0, PredictionError::NoClaimableWinnings);
+
+            amount
+        } else {
+            let (winning_position_shares, winning_market_shares) = match market.state {
+                MarketState::Passed => (position.pass_shares, market.pass_shares),
+                MarketState::Failed => (position.fail_shares, market.fail_shares),
+                _ => {
+                    let winning_outcome = usize::from(market.winning_outcome);
+
+                    (
+                        position.outcome_shares[winning_outcome],
+                        market.outcome_shares[winning_outcome],
+                    )
+                }
             };
 
-        require!(
-            winning_position_shares > 0,
-            PredictionError::NoClaimableWinnings
-        );
+            require!(
+                winning_position_shares > 0,
+                PredictionError::NoClaimableWinnings
+            );
 
-        let pct_of_pot_claimable = precise_number!(winning_position_shares.into())
-            .checked_div(&precise_number!(winning_market_shares.into()))
-            .unwrap();
-
-        let amount = imprecise_number!(pct_of_pot_claimable
-            .checked_mul(&precise_number!(ctx
-                .accounts
-                .market_token_account
-                .amount
-                .into()))
-            .unwrap()
-            .floor()
-            .unwrap()) as u64;
-
-        let price_update_v2_key = ctx.accounts.market.price_update_v2.key();
-        let resolve_ts_bytes = market.resolve_ts.to_le_bytes();
-        let signer_seeds: &[&[u8]] = market_signer!(
-            price_update_v2_key.as_ref(),
-            resolve_ts_bytes.as_ref(),
-            market.bump
-        );
+            get_pot_share(pot, winning_position_shares, winning_market_shares)?
+        };
+
+        // claims close the position, so the whole cost basis is realized and reported in an event
+        let cost_basis = position
+            .outcome_cost_basis
+            .iter()
+            .try_fold(
+                position
+                    .pass_cost_basis
+                    .checked_add(position.fail_cost_basis)
+                    .ok_or(PredictionError::MathOverflow)?,
+                |total, &cost_basis| total.checked_add(cost_basis),
+            )
+            .ok_or(PredictionError::MathOverflow)?;
+
+        emit!(WinningsClaimed {
+            authority: position.authority,
+            market: position.market,
+            amount,
+            realized_pnl: position
+                .realized_pnl
+                .checked_add(get_realized_pnl(amount, cost_basis)?)
+                .ok_or(PredictionError::MathOverflow)?,
+            total_fees_paid: position.total_fees_paid,
+        });
 
-        // return err!(PredictionError::CustomError);
+        let seeds = market.address_seeds();
+        let signer_seeds: &[&[u8]] = market_signer!(seeds, market.bump);
 
         transfer_checked(
             CpiContext::new(
@@ -111,3 +160,19 @@ impl ClaimWinnings<'_> {
         )
     }
 }
+
+/// Portion of `pot` owed to `position_shares` out of `market_shares`.
+fn get_pot_share(pot: u64, position_shares: u64, market_shares: u64) -> Result<u64> {
+    if position_shares == 0 {
+        return Ok(0);
+    }
+
+    let pct_of_pot_claimable = precise_number!(position_shares.into())
+        .checked_div(&precise_number!(market_shares.into()))
+        .ok_or(PredictionError::DivisionByZero)?;
+
+    to_u64(imprecise_number!(pct_of_pot_claimable
+        .checked_mul(&precise_number!(pot.into()))
+        .and_then(|amount| amount.floor())
+        .ok_or(PredictionError::MathOverflow)?))
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    error::PredictionError, utils::validate_market_args, Config, Market, ResolutionKind,
+    CONFIG_SEED, CONFIG_VERSION, MARKET_SEED,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateManualMarketArgs {
+    pub resolve_ts: i64,
+    /// Resolver settling the market, or `None` to settle through optimistic proposals
+    pub resolver: Option<Pubkey>,
+    pub title: String,
+}
+
+#[derive(Accounts)]
+#[instruction(args: CreateManualMarketArgs)]
+pub struct CreateManualMarket<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ PredictionError::InvalidConfigAuthority,
+        constraint = config.mint == trading_mint.key() @ PredictionError::InvalidTradingMint,
+        constraint = config.version == CONFIG_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = authority,
+        space = Market::space(args.title),
+        seeds = [MARKET_SEED, config.market_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub market: Account<'info, Market>,
+    pub trading_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = trading_mint,
+        associated_token::authority = market,
+        associated_token::token_program = token_program,
+    )]
+    pub market_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl CreateManualMarket<'_> {
+    pub fn handler(ctx: Context<CreateManualMarket>, args: CreateManualMarketArgs) -> Result<()> {
+        let CreateManualMarketArgs {
+            resolve_ts,
+            resolver,
+            title,
+        } = args;
+
+        require!(
+            resolver != Some(Pubkey::default()),
+            PredictionError::InvalidResolver
+        );
+
+        let config = &mut ctx.accounts.config;
+
+        validate_market_args(config, &ctx.accounts.trading_mint, resolve_ts, &title)?;
+
+        let id = config.market_count;
+
+        config.market_count = config
+            .market_count
+            .checked_add(1)
+            .ok_or(PredictionError::MathOverflow)?;
+
+        ctx.accounts.market.set_inner(Market {
+            resolution: if resolver.is_some() {
+                ResolutionKind::Manual
+            } else {
+                ResolutionKind::Optimistic
+            },
+            resolver: resolver.unwrap_or_default(),
+            ..Market::new(
+                id,
+                resolve_ts,
+                Pubkey::default(),
+                0.0,
+                config.share_price,
+                ctx.bumps.market,
+                title,
+            )
+        });
+
+        Ok(())
+    }
+}
@@ -3,9 +3,13 @@ use anchor_spl::{
     associated_token::AssociatedToken,
     token_interface::{Mint, TokenAccount, TokenInterface},
 };
-use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
-use crate::{error::PredictionError, Config, Market, MarketState, CONFIG_SEED, MARKET_SEED};
+use crate::{
+    error::PredictionError,
+    oracle::get_oracle_source,
+    utils::{validate_market_args, validate_target_price},
+    Config, Market, CONFIG_SEED, CONFIG_VERSION, MARKET_SEED,
+};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct CreateMarketArgs {
@@ -20,21 +24,24 @@ pub struct CreateMarket<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     #[account(
+        mut,
         seeds = [CONFIG_SEED],
         bump = config.bump,
         has_one = authority @ PredictionError::InvalidConfigAuthority,
         constraint = config.mint == trading_mint.key() @ PredictionError::InvalidTradingMint,
+        constraint = config.version == CONFIG_VERSION @ PredictionError::AccountNotMigrated,
     )]
     pub config: Account<'info, Config>,
     #[account(
         init,
         payer = authority,
         space = Market::space(args.title),
-        seeds = [MARKET_SEED, price_update_v2.key().as_ref(), args.resolve_ts.to_le_bytes().as_ref()],
+        seeds = [MARKET_SEED, config.market_count.to_le_bytes().as_ref()],
         bump,
     )]
     pub market: Account<'info, Market>,
-    pub price_update_v2: Account<'info, PriceUpdateV2>,
+    /// CHECK: Pyth price update or Switchboard pull feed, validated by `get_oracle_source`
+    pub price_update_v2: UncheckedAccount<'info>,
     pub trading_mint: InterfaceAccount<'info, Mint>,
     #[account(
         init_if_needed,
@@ -57,15 +64,30 @@ impl CreateMarket<'_> {
             title,
         } = args;
 
+        let config = &mut ctx.accounts.config;
+
+        validate_target_price(target_price)?;
+
+        validate_market_args(config, &ctx.accounts.trading_mint, resolve_ts, &title)?;
+
+        let id = config.market_count;
+
+        config.market_count = config
+            .market_count
+            .checked_add(1)
+            .ok_or(PredictionError::MathOverflow)?;
+
         ctx.accounts.market.set_inner(Market {
-            resolve_ts,
-            pass_shares: 0,
-            fail_shares: 0,
-            state: MarketState::Initialized,
-            price_update_v2: ctx.accounts.price_update_v2.key(),
-            target_price,
-            bump: ctx.bumps.market,
-            title,
+            oracle_source: get_oracle_source(&ctx.accounts.price_update_v2)?,
+            ..Market::new(
+                id,
+                resolve_ts,
+                ctx.accounts.price_update_v2.key(),
+                target_price,
+                config.share_price,
+                ctx.bumps.market,
+                title,
+            )
         });
 
         Ok(())
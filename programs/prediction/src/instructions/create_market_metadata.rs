@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PredictionError, Config, Market, MarketCategory, MarketMetadata, CONFIG_SEED,
+    CONFIG_VERSION, MARKET_METADATA_SEED, MARKET_METADATA_VERSION, MARKET_SEED, MARKET_VERSION,
+    MAX_DESCRIPTION_LEN, MAX_TAGS, MAX_TAG_LEN, MAX_URI_LEN,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct MarketMetadataArgs {
+    pub description: String,
+    pub category: MarketCategory,
+    pub tags: Vec<String>,
+    pub uri: String,
+}
+
+impl MarketMetadataArgs {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.description.len() <= MAX_DESCRIPTION_LEN,
+            PredictionError::DescriptionTooLong
+        );
+        require!(self.tags.len() <= MAX_TAGS, PredictionError::TooManyTags);
+        require!(
+            self.tags
+                .iter()
+                .all(|tag| !tag.is_empty() && tag.len() <= MAX_TAG_LEN),
+            PredictionError::InvalidTag
+        );
+        require!(self.uri.len() <= MAX_URI_LEN, PredictionError::UriTooLong);
+
+        Ok(())
+    }
+}
+
+/// Markets are only created by the config authority, directly or through its templates, so it
+/// is the creator that writes their metadata until the first trade.
+#[derive(Accounts)]
+#[instruction(args: MarketMetadataArgs)]
+pub struct CreateMarketMetadata<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ PredictionError::InvalidConfigAuthority,
+        constraint = config.version == CONFIG_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [
+            MARKET_SEED,
+            market.address_seeds()[0].as_ref(),
+            market.address_seeds()[1].as_ref(),
+        ],
+        bump = market.bump,
+        constraint = market.version == MARKET_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        init,
+        payer = authority,
+        space = MarketMetadata::space(&args.description, &args.tags, &args.uri),
+        seeds = [MARKET_METADATA_SEED, market.key().as_ref()],
+        bump,
+    )]
+    pub market_metadata: Account<'info, MarketMetadata>,
+    pub system_program: Program<'info, System>,
+}
+
+impl CreateMarketMetadata<'_> {
+    pub fn handler(ctx: Context<CreateMarketMetadata>, args: MarketMetadataArgs) -> Result<()> {
+        args.validate()?;
+
+        require!(
+            !ctx.accounts.market.has_traded(),
+            PredictionError::MarketAlreadyTraded
+        );
+
+        let MarketMetadataArgs {
+            description,
+            category,
+            tags,
+            uri,
+        } = args;
+
+        ctx.accounts.market_metadata.set_inner(MarketMetadata {
+            version: MARKET_METADATA_VERSION,
+            market: ctx.accounts.market.key(),
+            category,
+            bump: ctx.bumps.market_metadata,
+            reserved: [0; 64],
+            description,
+            tags,
+            uri,
+        });
+
+        Ok(())
+    }
+}
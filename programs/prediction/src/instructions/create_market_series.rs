@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{create_idempotent, AssociatedToken, Create},
+    token_interface::{Mint, TokenInterface},
+};
+
+use crate::{
+    error::PredictionError,
+    market_signer,
+    oracle::get_oracle_source,
+    utils::{create_pda_account, validate_market_args, validate_target_price},
+    Config, Market, MarketSeries, CONFIG_SEED, CONFIG_VERSION, MARKET_SEED, MARKET_SERIES_SEED,
+    MARKET_SERIES_VERSION, MAX_SERIES_MARKETS,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SeriesStrike {
+    pub target_price: f64,
+    pub title: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateMarketSeriesArgs {
+    pub resolve_ts: i64,
+    pub strikes: Vec<SeriesStrike>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: CreateMarketSeriesArgs)]
+pub struct CreateMarketSeries<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ PredictionError::InvalidConfigAuthority,
+        constraint = config.mint == trading_mint.key() @ PredictionError::InvalidTradingMint,
+        constraint = config.version == CONFIG_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = authority,
+        space = MarketSeries::space(args.strikes.len()),
+        seeds = [MARKET_SERIES_SEED, config.series_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub market_series: Account<'info, MarketSeries>,
+    /// CHECK: Pyth price update or Switchboard pull feed, validated by `get_oracle_source`
+    pub price_update_v2: UncheckedAccount<'info>,
+    pub trading_mint: InterfaceAccount<'info, Mint>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> CreateMarketSeries<'info> {
+    /// Remaining accounts are `[market, market_token_account]` pairs, one per strike in order.
+    pub fn handler(
+        ctx: Context<'_, '_, 'info, 'info, CreateMarketSeries<'info>>,
+        args: CreateMarketSeriesArgs,
+    ) -> Result<()> {
+        let CreateMarketSeriesArgs {
+            resolve_ts,
+            strikes,
+        } = args;
+
+        require!(
+            !strikes.is_empty() && strikes.len() <= MAX_SERIES_MARKETS,
+            PredictionError::InvalidSeriesLength
+        );
+        require!(
+            ctx.remaining_accounts.len() == strikes.len() * 2,
+            PredictionError::InvalidSeriesAccounts
+        );
+
+        let config = &mut ctx.accounts.config;
+        let series_key = ctx.accounts.market_series.key();
+        let price_update_v2_key = ctx.accounts.price_update_v2.key();
+        let oracle_source = get_oracle_source(&ctx.accounts.price_update_v2)?;
+        let mut markets = Vec::with_capacity(strikes.len());
+
+        for (
+            SeriesStrike {
+                target_price,
+                title,
+            },
+            accounts,
+        ) in strikes.into_iter().zip(ctx.remaining_accounts.chunks(2))
+        {
+            validate_target_price(target_price)?;
+            validate_market_args(config, &ctx.accounts.trading_mint, resolve_ts, &title)?;
+
+            let (market, market_token_account) = (&accounts[0], &accounts[1]);
+            let id = config.market_count;
+            let id_bytes = id.to_le_bytes();
+            let (market_key, bump) =
+                Pubkey::find_program_address(&[MARKET_SEED, id_bytes.as_ref()], &crate::ID);
+
+            require_keys_eq!(
+                market.key(),
+                market_key,
+                PredictionError::InvalidSeriesAccounts
+            );
+
+            create_pda_account(
+                market,
+                &ctx.accounts.authority.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                Market::space(title.clone()),
+                market_signer!([id_bytes.as_ref(), &[]], bump),
+            )?;
+
+            Market {
+                series: series_key,
+                oracle_source,
+                ..Market::new(
+                    id,
+                    resolve_ts,
+                    price_update_v2_key,
+                    target_price,
+                    config.share_price,
+                    bump,
+                    title,
+                )
+            }
+            .try_serialize(&mut &mut market.try_borrow_mut_data()?[..])?;
+
+            create_idempotent(CpiContext::new(
+                ctx.accounts.associated_token_program.to_account_info(),
+                Create {
+                    payer: ctx.accounts.authority.to_account_info(),
+                    associated_token: market_token_account.clone(),
+                    authority: market.clone(),
+                    mint: ctx.accounts.trading_mint.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                },
+            ))?;
+
+            config.market_count = config
+                .market_count
+                .checked_add(1)
+                .ok_or(PredictionError::MathOverflow)?;
+            markets.push(market_key);
+        }
+
+        let id = config.series_count;
+
+        config.series_count = config
+            .series_count
+            .checked_add(1)
+            .ok_or(PredictionError::MathOverflow)?;
+
+        ctx.accounts.market_series.set_inner(MarketSeries {
+            version: MARKET_SERIES_VERSION,
+            id,
+            price_update_v2: price_update_v2_key,
+            resolve_ts,
+            bump: ctx.bumps.market_series,
+            reserved: [0; 64],
+            markets,
+        });
+
+        Ok(())
+    }
+}
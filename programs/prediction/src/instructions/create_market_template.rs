@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PredictionError, oracle::get_oracle_source, utils::validate_target_price, Config,
+    MarketTemplate, PriceCondition, StrikeRule, CONFIG_SEED, CONFIG_VERSION, MARKET_TEMPLATE_SEED,
+    MARKET_TEMPLATE_VERSION, MAX_TITLE_LEN, ONE_IN_BASIS_POINTS,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateMarketTemplateArgs {
+    pub condition: PriceCondition,
+    pub duration: i64,
+    pub fee_bps: Option<u16>,
+    pub strike_rule: StrikeRule,
+    pub target_price: f64,
+    pub offset_bps: i16,
+    pub title: String,
+}
+
+#[derive(Accounts)]
+#[instruction(args: CreateMarketTemplateArgs)]
+pub struct CreateMarketTemplate<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ PredictionError::InvalidConfigAuthority,
+        constraint = config.version == CONFIG_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = authority,
+        space = MarketTemplate::space(&args.title),
+        seeds = [MARKET_TEMPLATE_SEED, config.template_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub market_template: Account<'info, MarketTemplate>,
+    /// CHECK: Pyth price update or Switchboard pull feed, validated by `get_oracle_source`
+    pub price_update_v2: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl CreateMarketTemplate<'_> {
+    pub fn handler(
+        ctx: Context<CreateMarketTemplate>,
+        args: CreateMarketTemplateArgs,
+    ) -> Result<()> {
+        let CreateMarketTemplateArgs {
+            condition,
+            duration,
+            fee_bps,
+            strike_rule,
+            target_price,
+            offset_bps,
+            title,
+        } = args;
+
+        let config = &mut ctx.accounts.config;
+
+        require!(
+            duration >= config.min_market_duration,
+            PredictionError::MarketDurationTooShort
+        );
+        require!(
+            duration <= config.max_market_duration,
+            PredictionError::MarketDurationTooLong
+        );
+        require!(
+            fee_bps.unwrap_or_default() <= ONE_IN_BASIS_POINTS,
+            PredictionError::InvalidFeeBps
+        );
+        validate_target_price(target_price)?;
+        get_oracle_source(&ctx.accounts.price_update_v2)?;
+        require!(
+            i32::from(offset_bps) > -i32::from(ONE_IN_BASIS_POINTS),
+            PredictionError::InvalidStrikeOffset
+        );
+        require!(!title.is_empty(), PredictionError::EmptyTitle);
+        require!(title.len() <= MAX_TITLE_LEN, PredictionError::TitleTooLong);
+
+        let id = config.template_count;
+
+        config.template_count = config
+            .template_count
+            .checked_add(1)
+            .ok_or(PredictionError::MathOverflow)?;
+
+        ctx.accounts.market_template.set_inner(MarketTemplate {
+            version: MARKET_TEMPLATE_VERSION,
+            id,
+            price_update_v2: ctx.accounts.price_update_v2.key(),
+            condition,
+            duration,
+            has_fee_override: fee_bps.is_some(),
+            fee_bps: fee_bps.unwrap_or_default(),
+            strike_rule,
+            target_price,
+            offset_bps,
+            last_market: Pubkey::default(),
+            spawn_count: 0,
+            bump: ctx.bumps.market_template,
+            reserved: [0; 64],
+            title,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    error::PredictionError,
+    oracle::get_oracle_source,
+    utils::{validate_market_args, validate_outcome_bounds},
+    Config, Market, MarketKind, CONFIG_SEED, CONFIG_VERSION, MARKET_SEED, MAX_OUTCOMES,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateScalarMarketArgs {
+    pub resolve_ts: i64,
+    pub lower: f64,
+    pub upper: f64,
+    pub title: String,
+}
+
+#[derive(Accounts)]
+#[instruction(args: CreateScalarMarketArgs)]
+pub struct CreateScalarMarket<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ PredictionError::InvalidConfigAuthority,
+        constraint = config.mint == trading_mint.key() @ PredictionError::InvalidTradingMint,
+        constraint = config.version == CONFIG_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = authority,
+        space = Market::space(args.title),
+        seeds = [MARKET_SEED, config.market_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub market: Account<'info, Market>,
+    /// CHECK: Pyth price update or Switchboard pull feed, validated by `get_oracle_source`
+    pub price_update_v2: UncheckedAccount<'info>,
+    pub trading_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = trading_mint,
+        associated_token::authority = market,
+        associated_token::token_program = token_program,
+    )]
+    pub market_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl CreateScalarMarket<'_> {
+    pub fn handler(ctx: Context<CreateScalarMarket>, args: CreateScalarMarketArgs) -> Result<()> {
+        let CreateScalarMarketArgs {
+            resolve_ts,
+            lower,
+            upper,
+            title,
+        } = args;
+
+        validate_outcome_bounds(&[lower, upper])?;
+
+        let config = &mut ctx.accounts.config;
+
+        validate_market_args(config, &ctx.accounts.trading_mint, resolve_ts, &title)?;
+
+        let id = config.market_count;
+
+        config.market_count = config
+            .market_count
+            .checked_add(1)
+            .ok_or(PredictionError::MathOverflow)?;
+
+        let mut bounds = [0.0; MAX_OUTCOMES - 1];
+        bounds[0] = lower;
+        bounds[1] = upper;
+
+        ctx.accounts.market.set_inner(Market {
+            kind: MarketKind::Scalar,
+            oracle_source: get_oracle_source(&ctx.accounts.price_update_v2)?,
+            bounds,
+            ..Market::new(
+                id,
+                resolve_ts,
+                ctx.accounts.price_update_v2.key(),
+                0.0,
+                config.share_price,
+                ctx.bumps.market,
+                title,
+            )
+        });
+
+        Ok(())
+    }
+}
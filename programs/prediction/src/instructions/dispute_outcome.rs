@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::{transfer_checked, TransferChecked},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    error::PredictionError, utils::get_transfer_inverse_fee, Config, Resolution, ResolutionState,
+    CONFIG_SEED, CONFIG_VERSION, RESOLUTION_SEED,
+};
+
+#[derive(Accounts)]
+pub struct DisputeOutcome<'info> {
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.mint == trading_mint.key() @ PredictionError::InvalidTradingMint,
+        constraint = config.version == CONFIG_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [RESOLUTION_SEED, resolution.market.as_ref()],
+        bump = resolution.bump,
+    )]
+    pub resolution: Account<'info, Resolution>,
+    pub trading_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = trading_mint,
+        associated_token::authority = challenger,
+        associated_token::token_program = token_program,
+    )]
+    pub challenger_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = trading_mint,
+        associated_token::authority = resolution,
+        associated_token::token_program = token_program,
+    )]
+    pub resolution_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl DisputeOutcome<'_> {
+    pub fn handler(ctx: Context<DisputeOutcome>) -> Result<()> {
+        let resolution = &mut ctx.accounts.resolution;
+
+        require!(
+            resolution.state == ResolutionState::Proposed,
+            PredictionError::InvalidResolutionState
+        );
+        require!(
+            Clock::get()?.unix_timestamp < resolution.dispute_deadline,
+            PredictionError::DisputeWindowClosed
+        );
+
+        resolution.state = ResolutionState::Disputed;
+        resolution.challenger = ctx.accounts.challenger.key();
+
+        let mint = ctx.accounts.trading_mint.to_account_info();
+        let bond = resolution.bond;
+
+        // challengers match the proposer's bond, covering any transfer fee
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    authority: ctx.accounts.challenger.to_account_info(),
+                    from: ctx.accounts.challenger_token_account.to_account_info(),
+                    mint: mint.clone(),
+                    to: ctx.accounts.resolution_token_account.to_account_info(),
+                },
+            ),
+            bond.checked_add(get_transfer_inverse_fee(&mint, bond)?)
+                .ok_or(PredictionError::MathOverflow)?,
+            ctx.accounts.trading_mint.decimals,
+        )
+    }
+}
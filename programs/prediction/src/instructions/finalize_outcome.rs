@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::{transfer_checked, TransferChecked},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    error::PredictionError, Config, Market, MarketKind, Resolution, ResolutionState, CONFIG_SEED,
+    CONFIG_VERSION, MARKET_SEED, MARKET_VERSION, RESOLUTION_SEED,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct FinalizeOutcomeArgs {
+    /// Outcome decided by the config authority, required once the proposal is disputed
+    pub outcome: Option<u8>,
+    /// Settled price decided by the config authority, required instead of `outcome` once the
+    /// proposal on a scalar market is disputed
+    pub settled_price: Option<f64>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeOutcome<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.mint == trading_mint.key() @ PredictionError::InvalidTradingMint,
+        constraint = config.version == CONFIG_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [
+            MARKET_SEED,
+            market.address_seeds()[0].as_ref(),
+            market.address_seeds()[1].as_ref(),
+        ],
+        bump = market.bump,
+        constraint = market.version == MARKET_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [RESOLUTION_SEED, market.key().as_ref()],
+        bump = resolution.bump,
+        has_one = market @ PredictionError::InvalidMarket,
+    )]
+    pub resolution: Account<'info, Resolution>,
+    pub trading_mint: InterfaceAccount<'info, Mint>,
+    /// Receives both bonds if disputed, otherwise the proposer's bond back
+    #[account(
+        mut,
+        token::mint = trading_mint,
+        token::token_program = token_program,
+    )]
+    pub winner_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = trading_mint,
+        associated_token::authority = resolution,
+        associated_token::token_program = token_program,
+    )]
+    pub resolution_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl FinalizeOutcome<'_> {
+    pub fn handler(ctx: Context<FinalizeOutcome>, args: FinalizeOutcomeArgs) -> Result<()> {
+        let resolution = &mut ctx.accounts.resolution;
+
+        let is_scalar = ctx.accounts.market.kind == MarketKind::Scalar;
+
+        let (outcome, settled_price) = match resolution.state {
+            ResolutionState::Proposed => {
+                require!(
+                    Clock::get()?.unix_timestamp >= resolution.dispute_deadline,
+                    PredictionError::DisputeWindowOpen
+                );
+
+                (
+                    resolution.proposed_outcome,
+                    is_scalar.then_some(resolution.proposed_price),
+                )
+            }
+            ResolutionState::Disputed => {
+                require_keys_eq!(
+                    ctx.accounts.authority.key(),
+                    ctx.accounts.config.authority,
+                    PredictionError::InvalidConfigAuthority
+                );
+
+                if is_scalar {
+                    let settled_price = args
+                        .settled_price
+                        .ok_or(PredictionError::MissingDisputeOutcome)?;
+
+                    (resolution.proposed_outcome, Some(settled_price))
+                } else {
+                    let outcome = args.outcome.ok_or(PredictionError::MissingDisputeOutcome)?;
+
+                    (outcome, args.settled_price)
+                }
+            }
+            ResolutionState::Finalized => return err!(PredictionError::InvalidResolutionState),
+        };
+
+        ctx.accounts
+            .market
+            .settle_proposal(outcome, settled_price)?;
+
+        // the losing side of a dispute forfeits its bond to the winner
+        let is_upheld = match settled_price {
+            Some(price) => price == resolution.proposed_price,
+            None => outcome == resolution.proposed_outcome,
+        };
+        let winner = if is_upheld {
+            resolution.proposer
+        } else {
+            resolution.challenger
+        };
+
+        require_keys_eq!(
+            ctx.accounts.winner_token_account.owner,
+            winner,
+            PredictionError::InvalidBondAccount
+        );
+
+        resolution.state = ResolutionState::Finalized;
+        resolution.final_outcome = outcome;
+        resolution.final_price = settled_price.unwrap_or_default();
+
+        let signer_seeds: &[&[u8]] = &[
+            RESOLUTION_SEED,
+            resolution.market.as_ref(),
+            &[resolution.bump],
+        ];
+
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    authority: resolution.to_account_info(),
+                    from: ctx.accounts.resolution_token_account.to_account_info(),
+                    mint: ctx.accounts.trading_mint.to_account_info(),
+                    to: ctx.accounts.winner_token_account.to_account_info(),
+                },
+            )
+            .with_signer(&[signer_seeds]),
+            ctx.accounts.resolution_token_account.amount,
+            ctx.accounts.trading_mint.decimals,
+        )
+    }
+}
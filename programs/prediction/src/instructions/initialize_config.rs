@@ -4,11 +4,19 @@ use anchor_spl::{
     token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
-use crate::{error::PredictionError, Config, CONFIG_SEED, ONE_IN_BASIS_POINTS};
+use crate::{
+    error::PredictionError,
+    utils::{get_share_price, validate_mint_extensions},
+    Config, CONFIG_SEED, CONFIG_VERSION, ONE_IN_BASIS_POINTS,
+};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct InitializeConfigArgs {
     pub fee_bps: u16,
+    pub min_market_duration: i64,
+    pub max_market_duration: i64,
+    pub proposal_bond: u64,
+    pub dispute_window: i64,
 }
 
 #[derive(Accounts)]
@@ -39,18 +47,42 @@ pub struct InitializeConfig<'info> {
 
 impl InitializeConfig<'_> {
     pub fn handler(ctx: Context<InitializeConfig>, args: InitializeConfigArgs) -> Result<()> {
-        let InitializeConfigArgs { fee_bps } = args;
+        let InitializeConfigArgs {
+            fee_bps,
+            min_market_duration,
+            max_market_duration,
+            proposal_bond,
+            dispute_window,
+        } = args;
 
         require!(
             fee_bps <= ONE_IN_BASIS_POINTS,
             PredictionError::InvalidFeeBps
         );
+        require!(
+            min_market_duration > 0 && min_market_duration <= max_market_duration,
+            PredictionError::InvalidMarketDuration
+        );
+        require!(proposal_bond > 0, PredictionError::InvalidProposalBond);
+        require!(dispute_window > 0, PredictionError::InvalidDisputeWindow);
+
+        validate_mint_extensions(&ctx.accounts.mint.to_account_info())?;
 
         ctx.accounts.config.set_inner(Config {
+            version: CONFIG_VERSION,
             authority: ctx.accounts.authority.key(),
             fee_bps,
             mint: ctx.accounts.mint.key(),
+            share_price: get_share_price(ctx.accounts.mint.decimals)?,
+            min_market_duration,
+            max_market_duration,
+            market_count: 0,
             bump: ctx.bumps.config,
+            series_count: 0,
+            template_count: 0,
+            proposal_bond,
+            dispute_window,
+            reserved: [0; 72],
         });
 
         Ok(())
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::PredictionError,
+    utils::{get_share_price, read_legacy_account, read_outdated_account, write_migrated_account},
+    Config, ConfigV0, CONFIG_SEED, CONFIG_VERSION, DEFAULT_DISPUTE_WINDOW,
+    DEFAULT_MAX_MARKET_DURATION, DEFAULT_MIN_MARKET_DURATION,
+};
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: Legacy layout is deserialized and validated in the handler
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump,
+        owner = crate::ID,
+    )]
+    pub config: UncheckedAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateConfig<'_> {
+    pub fn handler(ctx: Context<MigrateConfig>) -> Result<()> {
+        let config_info = ctx.accounts.config.to_account_info();
+        let share_price = get_share_price(ctx.accounts.mint.decimals)?;
+
+        // v1 configs only lack the fields appended since, which read as zero
+        let config = match read_legacy_account(&config_info, Config::DISCRIMINATOR) {
+            Ok(ConfigV0 {
+                authority,
+                fee_bps,
+                mint,
+                bump,
+            }) => Config {
+                version: CONFIG_VERSION,
+                authority,
+                fee_bps,
+                mint,
+                share_price,
+                bump,
+                min_market_duration: 0,
+                max_market_duration: 0,
+                market_count: 0,
+                series_count: 0,
+                template_count: 0,
+                proposal_bond: 0,
+                dispute_window: 0,
+                reserved: [0; 72],
+            },
+            Err(_) => read_outdated_account::<Config>(&config_info, CONFIG_VERSION)?,
+        };
+
+        require_keys_eq!(
+            config.authority,
+            ctx.accounts.authority.key(),
+            PredictionError::InvalidConfigAuthority
+        );
+        require_keys_eq!(
+            config.mint,
+            ctx.accounts.mint.key(),
+            PredictionError::InvalidTradingMint
+        );
+
+        write_migrated_account(
+            &config_info,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            Config::DISCRIMINATOR.len() + Config::INIT_SPACE,
+            &Config {
+                version: CONFIG_VERSION,
+                share_price,
+                min_market_duration: DEFAULT_MIN_MARKET_DURATION,
+                max_market_duration: DEFAULT_MAX_MARKET_DURATION,
+                // one whole token, matching the payout of a single share
+                proposal_bond: share_price,
+                dispute_window: DEFAULT_DISPUTE_WINDOW,
+                ..config
+            },
+        )
+    }
+}
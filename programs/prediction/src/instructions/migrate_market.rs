@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    error::PredictionError,
+    utils::{read_legacy_account, read_outdated_account, to_u64, write_migrated_account},
+    Config, Market, MarketSeeds, MarketV0, CONFIG_SEED, CONFIG_VERSION, MARKET_SEED,
+    MARKET_VERSION, MAX_OUTCOMES,
+};
+
+#[derive(Accounts)]
+pub struct MigrateMarket<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.mint == trading_mint.key() @ PredictionError::InvalidTradingMint,
+        constraint = config.version == CONFIG_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub config: Account<'info, Config>,
+    /// CHECK: Legacy layout is deserialized and validated in the handler
+    #[account(
+        mut,
+        owner = crate::ID,
+    )]
+    pub market: UncheckedAccount<'info>,
+    pub trading_mint: InterfaceAccount<'info, Mint>,
+    /// Required for markets that were traded, to carry over their collateral
+    #[account(
+        associated_token::mint = trading_mint,
+        associated_token::authority = market,
+        associated_token::token_program = token_program,
+    )]
+    pub market_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl MigrateMarket<'_> {
+    pub fn handler(ctx: Context<MigrateMarket>) -> Result<()> {
+        let market_info = ctx.accounts.market.to_account_info();
+
+        let config = &mut ctx.accounts.config;
+
+        // v1 markets only lack the fields appended since, which read as zero
+        let market = match read_legacy_account(&market_info, Market::DISCRIMINATOR) {
+            Ok(MarketV0 {
+                resolve_ts,
+                pass_shares,
+                fail_shares,
+                state,
+                price_update_v2,
+                target_price,
+                bump,
+                title,
+            }) => Market {
+                pass_shares,
+                fail_shares,
+                state,
+                ..Market::new(
+                    0,
+                    resolve_ts,
+                    price_update_v2,
+                    target_price,
+                    config.share_price,
+                    bump,
+                    title,
+                )
+            },
+            Err(_) => read_outdated_account::<Market>(&market_info, MARKET_VERSION)?,
+        };
+
+        let resolve_ts_bytes = market.resolve_ts.to_le_bytes();
+        let market_key = Pubkey::create_program_address(
+            &[
+                MARKET_SEED,
+                market.price_update_v2.as_ref(),
+                resolve_ts_bytes.as_ref(),
+                &[market.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| ErrorCode::ConstraintSeeds)?;
+
+        require_keys_eq!(market_key, market_info.key(), ErrorCode::ConstraintSeeds);
+
+        // legacy markets keep their original address and seeds, the id only keeps numbering unique
+        let id = config.market_count;
+
+        config.market_count = config
+            .market_count
+            .checked_add(1)
+            .ok_or(PredictionError::MathOverflow)?;
+
+        let total_shares = u128::from(market.pass_shares) + u128::from(market.fail_shares);
+
+        // legacy markets did not track collateral per outcome, so it is split by shares outstanding
+        let total_collateral = match &ctx.accounts.market_token_account {
+            Some(market_token_account) => market_token_account.amount,
+            None => {
+                require!(
+                    total_shares == 0,
+                    PredictionError::MissingMarketTokenAccount
+                );
+                0
+            }
+        };
+        let pass_collateral = (u128::from(total_collateral) * u128::from(market.pass_shares))
+            .checked_div(total_shares)
+            .map_or(Ok(0), to_u64)?;
+
+        let mut outcome_collateral = [0; MAX_OUTCOMES];
+        outcome_collateral[0] = pass_collateral;
+        outcome_collateral[1] = total_collateral - pass_collateral;
+
+        write_migrated_account(
+            &market_info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            Market::space(market.title.clone()),
+            &Market {
+                version: MARKET_VERSION,
+                id,
+                total_collateral,
+                outcome_collateral,
+                seeds: MarketSeeds::Legacy,
+                ..market
+            },
+        )
+    }
+}
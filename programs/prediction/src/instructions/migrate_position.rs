@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    utils::{read_legacy_account, write_migrated_account},
+    Position, PositionV0, MAX_OUTCOMES, POSITION_SEED, POSITION_VERSION,
+};
+
+#[derive(Accounts)]
+pub struct MigratePosition<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: Legacy layout is deserialized and validated in the handler
+    #[account(
+        mut,
+        owner = crate::ID,
+    )]
+    pub position: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl MigratePosition<'_> {
+    pub fn handler(ctx: Context<MigratePosition>) -> Result<()> {
+        let position_info = ctx.accounts.position.to_account_info();
+
+        let PositionV0 {
+            authority,
+            market,
+            pass_shares,
+            fail_shares,
+            bump,
+        } = read_legacy_account(&position_info, Position::DISCRIMINATOR)?;
+
+        let position_key = Pubkey::create_program_address(
+            &[POSITION_SEED, authority.as_ref(), market.as_ref(), &[bump]],
+            &crate::ID,
+        )
+        .map_err(|_| ErrorCode::ConstraintSeeds)?;
+
+        require_keys_eq!(
+            position_key,
+            position_info.key(),
+            ErrorCode::ConstraintSeeds
+        );
+
+        // cost basis of legacy positions is unknown, so it starts from zero
+        write_migrated_account(
+            &position_info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            Position::DISCRIMINATOR.len() + Position::INIT_SPACE,
+            &Position {
+                version: POSITION_VERSION,
+                authority,
+                market,
+                pass_shares,
+                fail_shares,
+                pass_cost_basis: 0,
+                fail_cost_basis: 0,
+                realized_pnl: 0,
+                total_fees_paid: 0,
+                bump,
+                outcome_shares: [0; MAX_OUTCOMES],
+                outcome_cost_basis: [0; MAX_OUTCOMES],
+                reserved: [0; 128],
+            },
+        )
+    }
+}
@@ -1,13 +1,51 @@
 pub mod claim_winnings;
+pub mod create_categorical_market;
+pub mod create_manual_market;
 pub mod create_market;
+pub mod create_market_metadata;
+pub mod create_market_series;
+pub mod create_market_template;
+pub mod create_scalar_market;
+pub mod dispute_outcome;
+pub mod finalize_outcome;
 pub mod initialize_config;
+pub mod migrate_config;
+pub mod migrate_market;
+pub mod migrate_position;
 pub mod open_position;
+pub mod propose_outcome;
+pub mod quote_trade;
+pub mod record_price_sample;
+pub mod resolve_market_manual;
+pub mod set_market_oracles;
+pub mod set_market_twap;
 pub mod settle_market;
+pub mod spawn_from_template;
 pub mod trade_shares;
+pub mod update_market_metadata;
 
 pub use claim_winnings::*;
+pub use create_categorical_market::*;
+pub use create_manual_market::*;
 pub use create_market::*;
+pub use create_market_metadata::*;
+pub use create_market_series::*;
+pub use create_market_template::*;
+pub use create_scalar_market::*;
+pub use dispute_outcome::*;
+pub use finalize_outcome::*;
 pub use initialize_config::*;
+pub use migrate_config::*;
+pub use migrate_market::*;
+pub use migrate_position::*;
 pub use open_position::*;
+pub use propose_outcome::*;
+pub use quote_trade::*;
+pub use record_price_sample::*;
+pub use resolve_market_manual::*;
+pub use set_market_oracles::*;
+pub use set_market_twap::*;
 pub use settle_market::*;
+pub use spawn_from_template::*;
 pub use trade_shares::*;
+pub use update_market_metadata::*;
@@ -1,14 +1,23 @@
 use anchor_lang::prelude::*;
 
-use crate::{Market, Position, MARKET_SEED, POSITION_SEED};
+use crate::{
+    error::PredictionError, Market, Position, MARKET_SEED, MARKET_VERSION, MAX_OUTCOMES,
+    POSITION_SEED, POSITION_VERSION,
+};
 
 #[derive(Accounts)]
 pub struct OpenPosition<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     #[account(
-        seeds = [MARKET_SEED, market.price_update_v2.key().as_ref(), market.resolve_ts.to_le_bytes().as_ref()],
+        mut,
+        seeds = [
+            MARKET_SEED,
+            market.address_seeds()[0].as_ref(),
+            market.address_seeds()[1].as_ref(),
+        ],
         bump = market.bump,
+        constraint = market.version == MARKET_VERSION @ PredictionError::AccountNotMigrated,
     )]
     pub market: Account<'info, Market>,
     #[account(
@@ -24,12 +33,28 @@ pub struct OpenPosition<'info> {
 
 impl OpenPosition<'_> {
     pub fn handler(ctx: Context<OpenPosition>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        // counts positions opened, so a trader who closes and reopens is counted again
+        market.unique_traders = market
+            .unique_traders
+            .checked_add(1)
+            .ok_or(PredictionError::MathOverflow)?;
+
         ctx.accounts.position.set_inner(Position {
+            version: POSITION_VERSION,
             authority: ctx.accounts.authority.key(),
             market: ctx.accounts.market.key(),
             pass_shares: 0,
             fail_shares: 0,
+            pass_cost_basis: 0,
+            fail_cost_basis: 0,
+            realized_pnl: 0,
+            total_fees_paid: 0,
             bump: ctx.bumps.position,
+            outcome_shares: [0; MAX_OUTCOMES],
+            outcome_cost_basis: [0; MAX_OUTCOMES],
+            reserved: [0; 128],
         });
 
         Ok(())
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::{transfer_checked, TransferChecked},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    error::PredictionError, utils::get_transfer_inverse_fee, Config, Market, MarketState,
+    Resolution, ResolutionKind, ResolutionState, CONFIG_SEED, CONFIG_VERSION, MARKET_SEED,
+    MARKET_VERSION, RESOLUTION_SEED, RESOLUTION_VERSION,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProposeOutcomeArgs {
+    /// Index of the proposed outcome, ignored for scalar markets
+    pub outcome: u8,
+    /// Settled price proposed for scalar markets, which must be omitted for the others
+    pub settled_price: Option<f64>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeOutcome<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.mint == trading_mint.key() @ PredictionError::InvalidTradingMint,
+        constraint = config.version == CONFIG_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [
+            MARKET_SEED,
+            market.address_seeds()[0].as_ref(),
+            market.address_seeds()[1].as_ref(),
+        ],
+        bump = market.bump,
+        constraint = market.version == MARKET_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        init,
+        payer = proposer,
+        space = Resolution::DISCRIMINATOR.len() + Resolution::INIT_SPACE,
+        seeds = [RESOLUTION_SEED, market.key().as_ref()],
+        bump,
+    )]
+    pub resolution: Account<'info, Resolution>,
+    pub trading_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = trading_mint,
+        associated_token::authority = proposer,
+        associated_token::token_program = token_program,
+    )]
+    pub proposer_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = proposer,
+        associated_token::mint = trading_mint,
+        associated_token::authority = resolution,
+        associated_token::token_program = token_program,
+    )]
+    pub resolution_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl ProposeOutcome<'_> {
+    pub fn handler(ctx: Context<ProposeOutcome>, args: ProposeOutcomeArgs) -> Result<()> {
+        let market = &ctx.accounts.market;
+
+        // oracle markets that missed the settlement window fall back to optimistic resolution
+        require!(
+            match market.resolution {
+                ResolutionKind::Optimistic => market.state == MarketState::Initialized,
+                ResolutionKind::Oracle => market.state == MarketState::Undecided,
+                ResolutionKind::Manual => false,
+            },
+            PredictionError::MarketNotProposable
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            now >= market.resolve_ts,
+            PredictionError::MarketCannotResolve
+        );
+
+        market.validate_proposal(args.outcome, args.settled_price)?;
+
+        let config = &ctx.accounts.config;
+
+        ctx.accounts.resolution.set_inner(Resolution {
+            version: RESOLUTION_VERSION,
+            market: market.key(),
+            state: ResolutionState::Proposed,
+            proposer: ctx.accounts.proposer.key(),
+            proposed_outcome: args.outcome,
+            proposed_ts: now,
+            dispute_deadline: now
+                .checked_add(config.dispute_window)
+                .ok_or(PredictionError::MathOverflow)?,
+            challenger: Pubkey::default(),
+            bond: config.proposal_bond,
+            final_outcome: 0,
+            bump: ctx.bumps.resolution,
+            proposed_price: args.settled_price.unwrap_or_default(),
+            final_price: 0.0,
+            reserved: [0; 48],
+        });
+
+        let mint = ctx.accounts.trading_mint.to_account_info();
+
+        // proposers cover any transfer fee so the resolution holds the full bond
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    authority: ctx.accounts.proposer.to_account_info(),
+                    from: ctx.accounts.proposer_token_account.to_account_info(),
+                    mint: mint.clone(),
+                    to: ctx.accounts.resolution_token_account.to_account_info(),
+                },
+            ),
+            config
+                .proposal_bond
+                .checked_add(get_transfer_inverse_fee(&mint, config.proposal_bond)?)
+                .ok_or(PredictionError::MathOverflow)?,
+            ctx.accounts.trading_mint.decimals,
+        )
+    }
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::PredictionError, utils::get_transfer_inverse_fee, Config, Market, MarketKind,
+    TradeSharesArgs, CONFIG_SEED, CONFIG_VERSION, MARKET_SEED, MARKET_VERSION,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct TradeQuote {
+    pub fee: u64,
+    /// Net price paid for a buy including any transfer fee, or received for a sale before it
+    pub amount: u64,
+    /// Price of the next PASS share after the trade, excluding fees
+    pub new_pass_price: u64,
+    /// Price of the next FAIL share after the trade, excluding fees
+    pub new_fail_price: u64,
+}
+
+/// Read-only quote of `trade_shares`, returned through return data for clients to simulate.
+#[derive(Accounts)]
+pub struct QuoteTrade<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.mint == trading_mint.key() @ PredictionError::InvalidTradingMint,
+        constraint = config.version == CONFIG_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [
+            MARKET_SEED,
+            market.address_seeds()[0].as_ref(),
+            market.address_seeds()[1].as_ref(),
+        ],
+        bump = market.bump,
+        constraint = market.version == MARKET_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub market: Account<'info, Market>,
+    pub trading_mint: InterfaceAccount<'info, Mint>,
+}
+
+impl QuoteTrade<'_> {
+    pub fn handler(ctx: Context<QuoteTrade>, args: TradeSharesArgs) -> Result<TradeQuote> {
+        let TradeSharesArgs {
+            shares,
+            is_pass,
+            is_buy,
+        } = args;
+
+        require!(shares > 0, PredictionError::InvalidTradeShares);
+
+        let market = &ctx.accounts.market;
+
+        require!(
+            matches!(market.kind, MarketKind::Binary | MarketKind::Scalar),
+            PredictionError::InvalidMarketKind
+        );
+        require!(
+            Clock::get()?.unix_timestamp < market.resolve_ts,
+            PredictionError::MarketResolved
+        );
+
+        // the trade is applied to a copy so the quote matches `trade_shares` exactly
+        let mut market = Market::clone(market);
+        let fee_bps = market.trade_fee_bps(ctx.accounts.config.fee_bps);
+        let (fee, mut amount) = market.trade_shares(shares, is_pass, is_buy, fee_bps)?;
+
+        // buyers cover any transfer fee, as in `trade_shares`
+        if is_buy {
+            amount = amount
+                .checked_add(get_transfer_inverse_fee(
+                    &ctx.accounts.trading_mint.to_account_info(),
+                    amount,
+                )?)
+                .ok_or(PredictionError::MathOverflow)?;
+        }
+
+        let (new_pass_price, new_fail_price) = market.spot_prices()?;
+
+        Ok(TradeQuote {
+            fee,
+            amount,
+            new_pass_price,
+            new_fail_price,
+        })
+    }
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PredictionError,
+    oracle::{get_market_price, OraclePrice},
+    Market, MarketState, PriceSample, PriceSamples, MARKET_SEED, MARKET_VERSION, MAX_PRICE_SAMPLES,
+    PRICE_SAMPLES_SEED,
+};
+
+/// Additional oracles of the market are passed as remaining accounts, as in `settle_market`.
+#[derive(Accounts)]
+pub struct RecordPriceSample<'info> {
+    #[account(
+        seeds = [
+            MARKET_SEED,
+            market.address_seeds()[0].as_ref(),
+            market.address_seeds()[1].as_ref(),
+        ],
+        bump = market.bump,
+        has_one = price_update_v2 @ PredictionError::InvalidPriceUpdateV2,
+        constraint = market.version == MARKET_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [PRICE_SAMPLES_SEED, market.key().as_ref()],
+        bump = price_samples.bump,
+    )]
+    pub price_samples: Account<'info, PriceSamples>,
+    /// CHECK: Pyth price update or Switchboard pull feed, validated by `get_oracle_source`
+    pub price_update_v2: UncheckedAccount<'info>,
+}
+
+impl RecordPriceSample<'_> {
+    pub fn handler(ctx: Context<RecordPriceSample>) -> Result<()> {
+        let market = &ctx.accounts.market;
+
+        require!(
+            market.state == MarketState::Initialized,
+            PredictionError::MarketAlreadySettled
+        );
+
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+
+        require!(
+            now >= market.resolve_ts - market.twap_window && now <= market.resolve_ts,
+            PredictionError::OutsideSamplingWindow
+        );
+
+        let price_samples = &mut ctx.accounts.price_samples;
+
+        // spacing samples across the window keeps a burst of samples from dominating the buffer
+        let min_interval = (market.twap_window / MAX_PRICE_SAMPLES as i64).max(1);
+
+        if let Some(last) = price_samples.last() {
+            require!(
+                now >= last.timestamp + min_interval,
+                PredictionError::PriceSampleTooSoon
+            );
+        }
+
+        let OraclePrice { price, exponent } = get_market_price(
+            market,
+            &ctx.accounts.price_update_v2,
+            ctx.remaining_accounts,
+            &clock,
+        )?;
+
+        price_samples.push(PriceSample {
+            price,
+            exponent,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PredictionError, Market, MarketState, ResolutionKind, MARKET_SEED, MARKET_VERSION,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ResolveMarketManualArgs {
+    /// Index of the winning outcome
+    pub outcome: u8,
+}
+
+#[derive(Accounts)]
+pub struct ResolveMarketManual<'info> {
+    pub resolver: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            MARKET_SEED,
+            market.address_seeds()[0].as_ref(),
+            market.address_seeds()[1].as_ref(),
+        ],
+        bump = market.bump,
+        has_one = resolver @ PredictionError::InvalidResolver,
+        constraint = market.version == MARKET_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+impl ResolveMarketManual<'_> {
+    pub fn handler(ctx: Context<ResolveMarketManual>, args: ResolveMarketManualArgs) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        require!(
+            market.resolution == ResolutionKind::Manual,
+            PredictionError::InvalidResolutionKind
+        );
+        require!(
+            market.state == MarketState::Initialized,
+            PredictionError::MarketAlreadySettled
+        );
+        require!(
+            Clock::get()?.unix_timestamp >= market.resolve_ts,
+            PredictionError::MarketCannotResolve
+        );
+
+        market.settle_outcome(args.outcome)
+    }
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PredictionError, oracle::get_oracle_source, Config, Market, MarketState, OracleSource,
+    ResolutionKind, CONFIG_SEED, CONFIG_VERSION, MARKET_SEED, MARKET_VERSION, MAX_EXTRA_ORACLES,
+    ONE_IN_BASIS_POINTS,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetMarketOraclesArgs {
+    /// Largest allowed spread between oracle prices, relative to their median
+    pub tolerance_bps: u16,
+}
+
+/// Additional oracles are passed as remaining accounts, replacing any previously set.
+#[derive(Accounts)]
+pub struct SetMarketOracles<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ PredictionError::InvalidConfigAuthority,
+        constraint = config.version == CONFIG_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [
+            MARKET_SEED,
+            market.address_seeds()[0].as_ref(),
+            market.address_seeds()[1].as_ref(),
+        ],
+        bump = market.bump,
+        constraint = market.version == MARKET_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+impl SetMarketOracles<'_> {
+    pub fn handler(ctx: Context<SetMarketOracles>, args: SetMarketOraclesArgs) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        require!(
+            market.resolution == ResolutionKind::Oracle,
+            PredictionError::InvalidResolutionKind
+        );
+        require!(
+            market.state == MarketState::Initialized,
+            PredictionError::MarketAlreadySettled
+        );
+        require!(!market.has_traded(), PredictionError::MarketAlreadyTraded);
+        require!(
+            args.tolerance_bps <= ONE_IN_BASIS_POINTS,
+            PredictionError::InvalidOracleTolerance
+        );
+
+        let oracles = ctx.remaining_accounts;
+
+        require!(
+            oracles.len() <= MAX_EXTRA_ORACLES,
+            PredictionError::InvalidOracleCount
+        );
+
+        let mut extra_oracles = [Pubkey::default(); MAX_EXTRA_ORACLES];
+        let mut extra_oracle_sources = [OracleSource::Pyth; MAX_EXTRA_ORACLES];
+
+        for (i, oracle) in oracles.iter().enumerate() {
+            require!(
+                oracle.key() != market.price_update_v2 && !extra_oracles[..i].contains(oracle.key),
+                PredictionError::InvalidOracleCount
+            );
+
+            extra_oracles[i] = oracle.key();
+            extra_oracle_sources[i] = get_oracle_source(oracle)?;
+        }
+
+        market.extra_oracle_count = oracles.len() as u8;
+        market.extra_oracles = extra_oracles;
+        market.extra_oracle_sources = extra_oracle_sources;
+        market.oracle_tolerance_bps = args.tolerance_bps;
+
+        Ok(())
+    }
+}
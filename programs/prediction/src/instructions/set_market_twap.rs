@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PredictionError, Config, Market, MarketState, PriceSample, PriceSamples, ResolutionKind,
+    CONFIG_SEED, CONFIG_VERSION, MARKET_SEED, MARKET_VERSION, MAX_PRICE_SAMPLES,
+    PRICE_SAMPLES_SEED, PRICE_SAMPLES_VERSION,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetMarketTwapArgs {
+    /// Seconds before the resolve timestamp in which price samples are recorded
+    pub window: i64,
+}
+
+#[derive(Accounts)]
+pub struct SetMarketTwap<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ PredictionError::InvalidConfigAuthority,
+        constraint = config.version == CONFIG_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [
+            MARKET_SEED,
+            market.address_seeds()[0].as_ref(),
+            market.address_seeds()[1].as_ref(),
+        ],
+        bump = market.bump,
+        constraint = market.version == MARKET_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        init,
+        payer = authority,
+        space = PriceSamples::DISCRIMINATOR.len() + PriceSamples::INIT_SPACE,
+        seeds = [PRICE_SAMPLES_SEED, market.key().as_ref()],
+        bump,
+    )]
+    pub price_samples: Account<'info, PriceSamples>,
+    pub system_program: Program<'info, System>,
+}
+
+impl SetMarketTwap<'_> {
+    pub fn handler(ctx: Context<SetMarketTwap>, args: SetMarketTwapArgs) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        require!(
+            market.resolution == ResolutionKind::Oracle,
+            PredictionError::InvalidResolutionKind
+        );
+        require!(
+            market.state == MarketState::Initialized,
+            PredictionError::MarketAlreadySettled
+        );
+        require!(!market.has_traded(), PredictionError::MarketAlreadyTraded);
+
+        let window_end = Clock::get()?
+            .unix_timestamp
+            .checked_add(args.window)
+            .ok_or(PredictionError::MathOverflow)?;
+
+        require!(
+            args.window > 0 && window_end <= market.resolve_ts,
+            PredictionError::InvalidTwapWindow
+        );
+
+        market.twap_window = args.window;
+
+        ctx.accounts.price_samples.set_inner(PriceSamples {
+            version: PRICE_SAMPLES_VERSION,
+            market: market.key(),
+            head: 0,
+            count: 0,
+            bump: ctx.bumps.price_samples,
+            samples: [PriceSample::default(); MAX_PRICE_SAMPLES],
+        });
+
+        Ok(())
+    }
+}
@@ -1,10 +1,8 @@
 use anchor_lang::prelude::*;
-use pyth_solana_receiver_sdk::price_update::{FeedId, Price, PriceUpdateV2};
-use spl_math::precise_number::PreciseNumber;
 
 use crate::{
-    error::PredictionError, imprecise_number, precise_number, Config, Market, MarketState,
-    CONFIG_SEED, MARKET_SEED, RESOLVE_TS_WINDOW,
+    error::PredictionError, oracle::get_market_price, Config, Market, MarketState, PriceSamples,
+    ResolutionKind, CONFIG_SEED, CONFIG_VERSION, MARKET_SEED, MARKET_VERSION, RESOLVE_TS_WINDOW,
 };
 
 #[derive(Accounts)]
@@ -14,22 +12,38 @@ pub struct SettleMarket<'info> {
         seeds = [CONFIG_SEED],
         bump = config.bump,
         has_one = authority @ PredictionError::InvalidConfigAuthority,
+        constraint = config.version == CONFIG_VERSION @ PredictionError::AccountNotMigrated,
     )]
     pub config: Account<'info, Config>,
     #[account(
         mut,
-        seeds = [MARKET_SEED, market.price_update_v2.key().as_ref(), market.resolve_ts.to_le_bytes().as_ref()],
+        seeds = [
+            MARKET_SEED,
+            market.address_seeds()[0].as_ref(),
+            market.address_seeds()[1].as_ref(),
+        ],
         bump = market.bump,
         has_one = price_update_v2 @ PredictionError::InvalidPriceUpdateV2,
+        constraint = market.version == MARKET_VERSION @ PredictionError::AccountNotMigrated,
     )]
     pub market: Account<'info, Market>,
-    pub price_update_v2: Account<'info, PriceUpdateV2>,
+    /// CHECK: Pyth price update or Switchboard pull feed, validated by `get_oracle_source`
+    pub price_update_v2: UncheckedAccount<'info>,
+    /// Required for markets settled against a TWAP
+    #[account(
+        has_one = market @ PredictionError::InvalidPriceSamples,
+    )]
+    pub price_samples: Option<Account<'info, PriceSamples>>,
 }
 
 impl SettleMarket<'_> {
     pub fn handler(ctx: Context<SettleMarket>) -> Result<()> {
         let market = &mut ctx.accounts.market;
 
+        require!(
+            market.resolution == ResolutionKind::Oracle,
+            PredictionError::InvalidResolutionKind
+        );
         require!(
             market.state == MarketState::Initialized,
             PredictionError::MarketAlreadySettled
@@ -43,37 +57,26 @@ impl SettleMarket<'_> {
             PredictionError::MarketCannotResolve
         );
 
-        if now - market.resolve_ts <= RESOLVE_TS_WINDOW {
-            let price_update_v2 = &ctx.accounts.price_update_v2;
-            let feed_id: FeedId = price_update_v2.price_message.feed_id.into();
-            let Price {
-                price,
-                exponent,
-                conf: _,
-                publish_time: _,
-            } = price_update_v2
-                .get_price_no_older_than(&clock, 60, &feed_id)
-                .unwrap();
-
-            let ops = if exponent < 0 {
-                PreciseNumber::checked_div
-            } else {
-                PreciseNumber::checked_mul
-            };
-
-            let scaled = ops(
-                &precise_number!(price as u128),
-                &precise_number!(10_u64.pow(exponent.abs() as u32) as u128),
-            )
-            .unwrap();
-
-            market.state = if !imprecise_number!(scaled) as f64 >= market.target_price {
-                MarketState::Passed
-            } else {
-                MarketState::Failed
-            }
+        let price = if market.twap_window > 0 {
+            ctx.accounts
+                .price_samples
+                .as_ref()
+                .ok_or(PredictionError::InvalidPriceSamples)?
+                .twap(market.resolve_ts)?
+        } else if now - market.resolve_ts <= RESOLVE_TS_WINDOW {
+            Some(get_market_price(
+                market,
+                &ctx.accounts.price_update_v2,
+                ctx.remaining_accounts,
+                &clock,
+            )?)
         } else {
-            market.state = MarketState::Undecided;
+            None
+        };
+
+        match price {
+            Some(price) => market.settle_price(&price),
+            None => market.state = MarketState::Undecided,
         }
 
         Ok(())
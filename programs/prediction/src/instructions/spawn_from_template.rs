@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    error::PredictionError,
+    oracle::get_oracle_source,
+    utils::{validate_market_args, validate_target_price},
+    Config, Market, MarketState, MarketTemplate, StrikeRule, CONFIG_SEED, CONFIG_VERSION,
+    MARKET_SEED, MARKET_TEMPLATE_SEED, MARKET_VERSION, ONE_IN_BASIS_POINTS,
+};
+
+#[derive(Accounts)]
+pub struct SpawnFromTemplate<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.mint == trading_mint.key() @ PredictionError::InvalidTradingMint,
+        constraint = config.version == CONFIG_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [MARKET_TEMPLATE_SEED, market_template.id.to_le_bytes().as_ref()],
+        bump = market_template.bump,
+        has_one = price_update_v2 @ PredictionError::InvalidPriceUpdateV2,
+    )]
+    pub market_template: Account<'info, MarketTemplate>,
+    #[account(
+        constraint = previous_market.key() == market_template.last_market @ PredictionError::InvalidPreviousMarket,
+        constraint = previous_market.version == MARKET_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub previous_market: Option<Account<'info, Market>>,
+    #[account(
+        init,
+        payer = payer,
+        space = Market::space(market_template.title.clone()),
+        seeds = [MARKET_SEED, config.market_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub market: Account<'info, Market>,
+    /// CHECK: Pyth price update or Switchboard pull feed, validated by `get_oracle_source`
+    pub price_update_v2: UncheckedAccount<'info>,
+    pub trading_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = trading_mint,
+        associated_token::authority = market,
+        associated_token::token_program = token_program,
+    )]
+    pub market_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl SpawnFromTemplate<'_> {
+    pub fn handler(ctx: Context<SpawnFromTemplate>) -> Result<()> {
+        let market_template = &mut ctx.accounts.market_template;
+        let previous_market = ctx.accounts.previous_market.as_ref();
+
+        require!(
+            previous_market.is_some() || market_template.last_market == Pubkey::default(),
+            PredictionError::InvalidPreviousMarket
+        );
+
+        if let Some(previous_market) = previous_market {
+            require!(
+                previous_market.state != MarketState::Initialized,
+                PredictionError::PreviousMarketNotSettled
+            );
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+
+        // keep the cadence of the previous market, skipping slots that have passed or are
+        // too close to meet the minimum market duration
+        let earliest_resolve_ts = now
+            .checked_add(ctx.accounts.config.min_market_duration)
+            .ok_or(PredictionError::MathOverflow)?;
+        let mut resolve_ts = previous_market
+            .map_or(now, |market| market.resolve_ts)
+            .checked_add(market_template.duration)
+            .ok_or(PredictionError::MathOverflow)?;
+        if resolve_ts < earliest_resolve_ts {
+            let skipped_slots =
+                (earliest_resolve_ts - resolve_ts - 1) / market_template.duration + 1;
+            resolve_ts = skipped_slots
+                .checked_mul(market_template.duration)
+                .and_then(|offset| resolve_ts.checked_add(offset))
+                .ok_or(PredictionError::MathOverflow)?;
+        }
+
+        let target_price = match (market_template.strike_rule, previous_market) {
+            (StrikeRule::LastSettlementOffset, Some(market)) if market.settled_price > 0.0 => {
+                market.settled_price
+                    * (f64::from(ONE_IN_BASIS_POINTS) + f64::from(market_template.offset_bps))
+                    / f64::from(ONE_IN_BASIS_POINTS)
+            }
+            _ => market_template.target_price,
+        };
+
+        let config = &mut ctx.accounts.config;
+
+        validate_target_price(target_price)?;
+
+        validate_market_args(
+            config,
+            &ctx.accounts.trading_mint,
+            resolve_ts,
+            &market_template.title,
+        )?;
+
+        let id = config.market_count;
+
+        config.market_count = config
+            .market_count
+            .checked_add(1)
+            .ok_or(PredictionError::MathOverflow)?;
+
+        ctx.accounts.market.set_inner(Market {
+            condition: market_template.condition,
+            oracle_source: get_oracle_source(&ctx.accounts.price_update_v2)?,
+            has_fee_override: market_template.has_fee_override,
+            fee_bps: market_template.fee_bps,
+            template: market_template.key(),
+            ..Market::new(
+                id,
+                resolve_ts,
+                market_template.price_update_v2,
+                target_price,
+                config.share_price,
+                ctx.bumps.market,
+                market_template.title.clone(),
+            )
+        });
+
+        market_template.last_market = ctx.accounts.market.key();
+        market_template.spawn_count = market_template
+            .spawn_count
+            .checked_add(1)
+            .ok_or(PredictionError::MathOverflow)?;
+
+        Ok(())
+    }
+}
@@ -6,8 +6,10 @@ use anchor_spl::{
 };
 
 use crate::{
-    error::PredictionError, market_signer, utils::calculate_price, Config, Market, Position,
-    CONFIG_SEED, MARKET_SEED,
+    error::PredictionError,
+    market_signer,
+    utils::{calculate_outcome_price, get_realized_pnl, get_transfer_inverse_fee},
+    Config, Market, MarketKind, Position, CONFIG_SEED, CONFIG_VERSION, MARKET_SEED, MARKET_VERSION,
 };
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -17,6 +19,13 @@ pub struct TradeSharesArgs {
     pub is_buy: bool,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct TradeOutcomeSharesArgs {
+    pub shares: u64,
+    pub outcome: u8,
+    pub is_buy: bool,
+}
+
 #[derive(Accounts)]
 pub struct TradeShares<'info> {
     #[account(mut)]
@@ -27,12 +36,18 @@ pub struct TradeShares<'info> {
         seeds = [CONFIG_SEED],
         bump = config.bump,
         constraint = config.authority == config_authority.key() @ PredictionError::InvalidConfigAuthority,
+        constraint = config.version == CONFIG_VERSION @ PredictionError::AccountNotMigrated,
     )]
     pub config: Account<'info, Config>,
     #[account(
         mut,
-        seeds = [MARKET_SEED, market.price_update_v2.key().as_ref(), market.resolve_ts.to_le_bytes().as_ref()],
+        seeds = [
+            MARKET_SEED,
+            market.address_seeds()[0].as_ref(),
+            market.address_seeds()[1].as_ref(),
+        ],
         bump = market.bump,
+        constraint = market.version == MARKET_VERSION @ PredictionError::AccountNotMigrated,
     )]
     pub market: Account<'info, Market>,
     #[account(
@@ -79,80 +94,207 @@ impl TradeShares<'_> {
 
         require!(shares > 0, PredictionError::InvalidTradeShares);
 
+        let fee_bps = ctx.accounts.fee_bps();
         let market = &mut ctx.accounts.market;
-        let now = Clock::get()?.unix_timestamp;
 
-        require!(now < market.resolve_ts, PredictionError::MarketResolved);
+        require!(
+            matches!(market.kind, MarketKind::Binary | MarketKind::Scalar),
+            PredictionError::InvalidMarketKind
+        );
+        require!(
+            Clock::get()?.unix_timestamp < market.resolve_ts,
+            PredictionError::MarketResolved
+        );
 
+        let position = &mut ctx.accounts.position;
+        let position_shares = if is_pass {
+            &mut position.pass_shares
+        } else {
+            &mut position.fail_shares
+        };
+
+        *position_shares = if is_buy {
+            position_shares
+                .checked_add(shares)
+                .ok_or(PredictionError::MathOverflow)?
+        } else {
+            position_shares
+                .checked_sub(shares)
+                .ok_or(PredictionError::InsufficientSharesToSell)?
+        };
+
+        let (fee, amount) = market.trade_shares(shares, is_pass, is_buy, fee_bps)?;
+
+        let position: &mut Position = &mut ctx.accounts.position;
+        let (remaining_shares, cost_basis) = if is_pass {
+            (position.pass_shares, &mut position.pass_cost_basis)
+        } else {
+            (position.fail_shares, &mut position.fail_cost_basis)
+        };
+
+        update_cost_basis(
+            cost_basis,
+            &mut position.realized_pnl,
+            remaining_shares,
+            shares,
+            amount,
+            fee,
+            is_buy,
+        )?;
+
+        ctx.accounts.execute_trade(amount, fee, is_buy)
+    }
+
+    pub fn trade_outcome_shares(
+        ctx: Context<TradeShares>,
+        args: TradeOutcomeSharesArgs,
+    ) -> Result<()> {
+        let TradeOutcomeSharesArgs {
+            shares,
+            outcome,
+            is_buy,
+        } = args;
+
+        require!(shares > 0, PredictionError::InvalidTradeShares);
+
+        let fee_bps = ctx.accounts.fee_bps();
+        let market = &mut ctx.accounts.market;
+
+        require!(
+            market.kind == MarketKind::Categorical,
+            PredictionError::InvalidMarketKind
+        );
+        require!(
+            Clock::get()?.unix_timestamp < market.resolve_ts,
+            PredictionError::MarketResolved
+        );
+        require!(
+            outcome < market.outcome_count,
+            PredictionError::InvalidOutcome
+        );
+
+        let outcome = usize::from(outcome);
         let position = &mut ctx.accounts.position;
 
         if is_buy {
-            if is_pass {
-                position.pass_shares = position.pass_shares.checked_add(shares).unwrap();
-                market.pass_shares = market.pass_shares.checked_add(shares).unwrap();
-            } else {
-                position.fail_shares = position.fail_shares.checked_add(shares).unwrap();
-                market.fail_shares = market.fail_shares.checked_add(shares).unwrap();
-            }
+            position.outcome_shares[outcome] = position.outcome_shares[outcome]
+                .checked_add(shares)
+                .ok_or(PredictionError::MathOverflow)?;
+            market.outcome_shares[outcome] = market.outcome_shares[outcome]
+                .checked_add(shares)
+                .ok_or(PredictionError::MathOverflow)?;
         } else {
-            if is_pass {
-                position.pass_shares = position
-                    .pass_shares
-                    .checked_sub(shares)
-                    .ok_or(PredictionError::InsufficientSharesToSell)?;
-                market.pass_shares = market
-                    .pass_shares
-                    .checked_sub(shares)
-                    .ok_or(PredictionError::InsufficientSharesToSell)?;
-            } else {
-                position.fail_shares = position
-                    .fail_shares
-                    .checked_sub(shares)
-                    .ok_or(PredictionError::InsufficientSharesToSell)?;
-                market.fail_shares = market
-                    .fail_shares
-                    .checked_sub(shares)
-                    .ok_or(PredictionError::InsufficientSharesToSell)?;
-            }
+            position.outcome_shares[outcome] = position.outcome_shares[outcome]
+                .checked_sub(shares)
+                .ok_or(PredictionError::InsufficientSharesToSell)?;
+            market.outcome_shares[outcome] = market.outcome_shares[outcome]
+                .checked_sub(shares)
+                .ok_or(PredictionError::InsufficientSharesToSell)?;
         }
 
-        let config = &ctx.accounts.config;
-        let (fee, amount) = calculate_price(
+        let (fee, amount) = calculate_outcome_price(
             shares,
-            market.pass_shares,
-            market.fail_shares,
-            is_pass,
-            config.fee_bps,
+            &market.outcome_shares[..usize::from(market.outcome_count)],
+            outcome,
+            fee_bps,
+            market.share_price,
+        )?;
+
+        let (fee, amount) = if is_buy {
+            market.add_collateral(outcome, amount)?;
+            (fee, amount)
+        } else {
+            let outcome_shares = market.outcome_shares[outcome];
+
+            market.withdraw_collateral(
+                outcome,
+                shares,
+                outcome_shares + shares,
+                (fee, amount),
+                fee_bps,
+            )?
+        };
+
+        let position: &mut Position = &mut ctx.accounts.position;
+
+        update_cost_basis(
+            &mut position.outcome_cost_basis[outcome],
+            &mut position.realized_pnl,
+            position.outcome_shares[outcome],
+            shares,
+            amount,
+            fee,
+            is_buy,
         )?;
 
-        let price_update_v2_key = market.price_update_v2.key();
-        let resolve_ts_bytes = market.resolve_ts.to_le_bytes();
-        let signer_seeds: &[&[&[u8]]] = &[market_signer!(
-            price_update_v2_key.as_ref(),
-            resolve_ts_bytes.as_ref(),
-            market.bump
-        )];
+        ctx.accounts.execute_trade(amount, fee, is_buy)
+    }
+
+    fn fee_bps(&self) -> u16 {
+        self.market.trade_fee_bps(self.config.fee_bps)
+    }
+
+    /// Records trade stats and moves `amount` and `fee` between the trader, market and config.
+    fn execute_trade(&mut self, amount: u64, fee: u64, is_buy: bool) -> Result<()> {
+        let market = &mut self.market;
+        let now = Clock::get()?.unix_timestamp;
+
+        self.position.total_fees_paid = self
+            .position
+            .total_fees_paid
+            .checked_add(fee)
+            .ok_or(PredictionError::MathOverflow)?;
+
+        market.volume = market
+            .volume
+            .checked_add(
+                amount
+                    .checked_add(fee)
+                    .ok_or(PredictionError::MathOverflow)?,
+            )
+            .ok_or(PredictionError::MathOverflow)?;
+        market.fees_collected = market
+            .fees_collected
+            .checked_add(fee)
+            .ok_or(PredictionError::MathOverflow)?;
+        market.trade_count = market
+            .trade_count
+            .checked_add(1)
+            .ok_or(PredictionError::MathOverflow)?;
+        market.last_trade_ts = now;
+
+        let seeds = market.address_seeds();
+        let signer_seeds: &[&[&[u8]]] = &[market_signer!(seeds, market.bump)];
 
-        let mint = ctx.accounts.trading_mint.to_account_info();
-        let decimals = ctx.accounts.trading_mint.decimals;
+        let mint = self.trading_mint.to_account_info();
+        let decimals = self.trading_mint.decimals;
+
+        // buyers cover any transfer fee so the market receives the full price
+        let transfer_amount = if is_buy {
+            amount
+                .checked_add(get_transfer_inverse_fee(&mint, amount)?)
+                .ok_or(PredictionError::MathOverflow)?
+        } else {
+            amount
+        };
 
         let (authority, from, to) = if is_buy {
             (
-                ctx.accounts.authority.to_account_info(),
-                ctx.accounts.authority_token_account.to_account_info(),
-                ctx.accounts.market_token_account.to_account_info(),
+                self.authority.to_account_info(),
+                self.authority_token_account.to_account_info(),
+                self.market_token_account.to_account_info(),
             )
         } else {
             (
                 market.to_account_info(),
-                ctx.accounts.market_token_account.to_account_info(),
-                ctx.accounts.authority_token_account.to_account_info(),
+                self.market_token_account.to_account_info(),
+                self.authority_token_account.to_account_info(),
             )
         };
 
         {
             let mut cpi_context = CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
+                self.token_program.to_account_info(),
                 TransferChecked {
                     authority: authority.clone(),
                     from: from.clone(),
@@ -165,20 +307,17 @@ impl TradeShares<'_> {
                 cpi_context = cpi_context.with_signer(signer_seeds);
             }
 
-            transfer_checked(cpi_context, amount, decimals)?;
+            transfer_checked(cpi_context, transfer_amount, decimals)?;
         }
 
         {
             let mut cpi_context = CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
+                self.token_program.to_account_info(),
                 TransferChecked {
                     authority,
                     from,
                     mint: mint.to_account_info(),
-                    to: ctx
-                        .accounts
-                        .config_authority_token_account
-                        .to_account_info(),
+                    to: self.config_authority_token_account.to_account_info(),
                 },
             );
 
@@ -189,6 +328,54 @@ impl TradeShares<'_> {
             transfer_checked(cpi_context, fee, decimals)?;
         };
 
+        self.market_token_account.reload()?;
+
+        require!(
+            self.market_token_account.amount >= self.market.total_collateral,
+            PredictionError::InsolventMarket
+        );
+
         Ok(())
     }
 }
+
+/// Adds a buy to `cost_basis`, or releases it proportionally to the shares sold and realizes PnL.
+fn update_cost_basis(
+    cost_basis: &mut u64,
+    realized_pnl: &mut i64,
+    remaining_shares: u64,
+    shares: u64,
+    amount: u64,
+    fee: u64,
+    is_buy: bool,
+) -> Result<()> {
+    if is_buy {
+        *cost_basis = cost_basis
+            .checked_add(
+                amount
+                    .checked_add(fee)
+                    .ok_or(PredictionError::MathOverflow)?,
+            )
+            .ok_or(PredictionError::MathOverflow)?;
+    } else {
+        let released_cost_basis = (*cost_basis as u128)
+            .checked_mul(shares.into())
+            .ok_or(PredictionError::MathOverflow)?
+            .checked_div(
+                remaining_shares
+                    .checked_add(shares)
+                    .ok_or(PredictionError::MathOverflow)?
+                    .into(),
+            )
+            .ok_or(PredictionError::DivisionByZero)? as u64;
+
+        *cost_basis = cost_basis
+            .checked_sub(released_cost_basis)
+            .ok_or(PredictionError::MathOverflow)?;
+        *realized_pnl = realized_pnl
+            .checked_add(get_realized_pnl(amount, released_cost_basis)?)
+            .ok_or(PredictionError::MathOverflow)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PredictionError, Config, Market, MarketMetadata, MarketMetadataArgs, CONFIG_SEED,
+    CONFIG_VERSION, MARKET_METADATA_SEED, MARKET_SEED, MARKET_VERSION,
+};
+
+/// Checked against the config authority as the creator of every market, see
+/// `CreateMarketMetadata`.
+#[derive(Accounts)]
+#[instruction(args: MarketMetadataArgs)]
+pub struct UpdateMarketMetadata<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ PredictionError::InvalidConfigAuthority,
+        constraint = config.version == CONFIG_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [
+            MARKET_SEED,
+            market.address_seeds()[0].as_ref(),
+            market.address_seeds()[1].as_ref(),
+        ],
+        bump = market.bump,
+        constraint = market.version == MARKET_VERSION @ PredictionError::AccountNotMigrated,
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        realloc = MarketMetadata::space(&args.description, &args.tags, &args.uri),
+        realloc::payer = authority,
+        realloc::zero = false,
+        seeds = [MARKET_METADATA_SEED, market.key().as_ref()],
+        bump = market_metadata.bump,
+        has_one = market @ PredictionError::InvalidMarket,
+    )]
+    pub market_metadata: Account<'info, MarketMetadata>,
+    pub system_program: Program<'info, System>,
+}
+
+impl UpdateMarketMetadata<'_> {
+    pub fn handler(ctx: Context<UpdateMarketMetadata>, args: MarketMetadataArgs) -> Result<()> {
+        args.validate()?;
+
+        require!(
+            !ctx.accounts.market.has_traded(),
+            PredictionError::MarketAlreadyTraded
+        );
+
+        let MarketMetadataArgs {
+            description,
+            category,
+            tags,
+            uri,
+        } = args;
+
+        let market_metadata = &mut ctx.accounts.market_metadata;
+
+        market_metadata.description = description;
+        market_metadata.category = category;
+        market_metadata.tags = tags;
+        market_metadata.uri = uri;
+
+        Ok(())
+    }
+}
@@ -1,13 +1,16 @@
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod macros;
+pub mod oracle;
 pub mod state;
 pub mod utils;
 
 use anchor_lang::prelude::*;
 
 pub use constants::*;
+pub use events::*;
 pub use instructions::*;
 pub use state::*;
 
@@ -28,6 +31,70 @@ pub mod prediction {
         CreateMarket::handler(ctx, args)
     }
 
+    pub fn create_categorical_market(
+        ctx: Context<CreateCategoricalMarket>,
+        args: CreateCategoricalMarketArgs,
+    ) -> Result<()> {
+        CreateCategoricalMarket::handler(ctx, args)
+    }
+
+    pub fn create_scalar_market(
+        ctx: Context<CreateScalarMarket>,
+        args: CreateScalarMarketArgs,
+    ) -> Result<()> {
+        CreateScalarMarket::handler(ctx, args)
+    }
+
+    pub fn create_manual_market(
+        ctx: Context<CreateManualMarket>,
+        args: CreateManualMarketArgs,
+    ) -> Result<()> {
+        CreateManualMarket::handler(ctx, args)
+    }
+
+    pub fn create_market_metadata(
+        ctx: Context<CreateMarketMetadata>,
+        args: MarketMetadataArgs,
+    ) -> Result<()> {
+        CreateMarketMetadata::handler(ctx, args)
+    }
+
+    pub fn update_market_metadata(
+        ctx: Context<UpdateMarketMetadata>,
+        args: MarketMetadataArgs,
+    ) -> Result<()> {
+        UpdateMarketMetadata::handler(ctx, args)
+    }
+
+    pub fn create_market_series<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateMarketSeries<'info>>,
+        args: CreateMarketSeriesArgs,
+    ) -> Result<()> {
+        CreateMarketSeries::handler(ctx, args)
+    }
+
+    pub fn create_market_template(
+        ctx: Context<CreateMarketTemplate>,
+        args: CreateMarketTemplateArgs,
+    ) -> Result<()> {
+        CreateMarketTemplate::handler(ctx, args)
+    }
+
+    pub fn spawn_from_template(ctx: Context<SpawnFromTemplate>) -> Result<()> {
+        SpawnFromTemplate::handler(ctx)
+    }
+
+    pub fn set_market_oracles(
+        ctx: Context<SetMarketOracles>,
+        args: SetMarketOraclesArgs,
+    ) -> Result<()> {
+        SetMarketOracles::handler(ctx, args)
+    }
+
+    pub fn set_market_twap(ctx: Context<SetMarketTwap>, args: SetMarketTwapArgs) -> Result<()> {
+        SetMarketTwap::handler(ctx, args)
+    }
+
     pub fn open_position(ctx: Context<OpenPosition>) -> Result<()> {
         OpenPosition::handler(ctx)
     }
@@ -36,11 +103,60 @@ pub mod prediction {
         TradeShares::trade_shares(ctx, args)
     }
 
+    pub fn quote_trade(ctx: Context<QuoteTrade>, args: TradeSharesArgs) -> Result<TradeQuote> {
+        QuoteTrade::handler(ctx, args)
+    }
+
+    pub fn trade_outcome_shares(
+        ctx: Context<TradeShares>,
+        args: TradeOutcomeSharesArgs,
+    ) -> Result<()> {
+        TradeShares::trade_outcome_shares(ctx, args)
+    }
+
+    pub fn record_price_sample(ctx: Context<RecordPriceSample>) -> Result<()> {
+        RecordPriceSample::handler(ctx)
+    }
+
     pub fn settle_market(ctx: Context<SettleMarket>) -> Result<()> {
         SettleMarket::handler(ctx)
     }
 
+    pub fn resolve_market_manual(
+        ctx: Context<ResolveMarketManual>,
+        args: ResolveMarketManualArgs,
+    ) -> Result<()> {
+        ResolveMarketManual::handler(ctx, args)
+    }
+
+    pub fn propose_outcome(ctx: Context<ProposeOutcome>, args: ProposeOutcomeArgs) -> Result<()> {
+        ProposeOutcome::handler(ctx, args)
+    }
+
+    pub fn dispute_outcome(ctx: Context<DisputeOutcome>) -> Result<()> {
+        DisputeOutcome::handler(ctx)
+    }
+
+    pub fn finalize_outcome(
+        ctx: Context<FinalizeOutcome>,
+        args: FinalizeOutcomeArgs,
+    ) -> Result<()> {
+        FinalizeOutcome::handler(ctx, args)
+    }
+
     pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
         ClaimWinnings::handler(ctx)
     }
+
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        MigrateConfig::handler(ctx)
+    }
+
+    pub fn migrate_market(ctx: Context<MigrateMarket>) -> Result<()> {
+        MigrateMarket::handler(ctx)
+    }
+
+    pub fn migrate_position(ctx: Context<MigratePosition>) -> Result<()> {
+        MigratePosition::handler(ctx)
+    }
 }
@@ -1,20 +1,23 @@
 #[macro_export]
 macro_rules! market_signer {
-    ($prediction_mint: expr, $resolve_ts: expr, $bump: expr) => {
-        &[MARKET_SEED, $prediction_mint, $resolve_ts, &[$bump]]
+    ($seeds: expr, $bump: expr) => {
+        &[MARKET_SEED, &$seeds[0], &$seeds[1], &[$bump]]
     };
 }
 
 #[macro_export]
 macro_rules! precise_number {
     ($value: expr) => {
-        spl_math::precise_number::PreciseNumber::new($value).unwrap()
+        spl_math::precise_number::PreciseNumber::new($value)
+            .ok_or($crate::error::PredictionError::MathOverflow)?
     };
 }
 
 #[macro_export]
 macro_rules! imprecise_number {
     ($precise_number: expr) => {
-        $precise_number.to_imprecise().unwrap()
+        $precise_number
+            .to_imprecise()
+            .ok_or($crate::error::PredictionError::MathOverflow)?
     };
 }
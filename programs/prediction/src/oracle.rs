@@ -0,0 +1,210 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::{
+    error::GetPriceError,
+    price_update::{FeedId, Price, PriceUpdateV2},
+};
+
+use crate::{error::PredictionError, Market, OracleSource, ONE_IN_BASIS_POINTS};
+
+pub const SWITCHBOARD_ON_DEMAND_PROGRAM_ID: Pubkey =
+    pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+
+/// Anchor discriminator of Switchboard on-demand `PullFeedAccountData`.
+const PULL_FEED_DISCRIMINATOR: [u8; 8] = [196, 27, 108, 196, 10, 215, 219, 40];
+const PULL_FEED_LEN: usize = 8 + 3_200;
+/// Offset of `last_update_timestamp: i64`, after 32 submissions and the feed configuration.
+const PULL_FEED_LAST_UPDATE_TIMESTAMP_OFFSET: usize = 8 + 2_208;
+/// Offset of `result.value: i128`, the median of the latest submissions.
+const PULL_FEED_RESULT_VALUE_OFFSET: usize = 8 + 2_256;
+/// Switchboard values are fixed-point with 18 decimals.
+const SWITCHBOARD_EXPONENT: i32 = -18;
+
+/// Maximum age in seconds of an oracle price used for settlement.
+pub const MAX_PRICE_AGE: u64 = 60;
+
+pub struct OraclePrice {
+    pub price: i128,
+    pub exponent: i32,
+}
+
+impl OraclePrice {
+    /// Price as a float, dividing by the exact power of ten so prices equal to a target compare equal.
+    pub fn to_f64(&self) -> f64 {
+        let scale = 10_f64.powi(self.exponent.abs());
+
+        if self.exponent < 0 {
+            self.price as f64 / scale
+        } else {
+            self.price as f64 * scale
+        }
+    }
+}
+
+/// Oracle source of a price account, determined by its owning program.
+pub fn get_oracle_source(oracle: &AccountInfo) -> Result<OracleSource> {
+    let source = if oracle.owner == &pyth_solana_receiver_sdk::ID {
+        OracleSource::Pyth
+    } else if oracle.owner == &SWITCHBOARD_ON_DEMAND_PROGRAM_ID {
+        OracleSource::Switchboard
+    } else {
+        return err!(PredictionError::InvalidPriceUpdateV2);
+    };
+
+    // fail early on accounts that are not price feeds of the owning program
+    let data = oracle.try_borrow_data()?;
+
+    match source {
+        OracleSource::Pyth => {
+            PriceUpdateV2::try_deserialize(&mut &data[..])?;
+        }
+        OracleSource::Switchboard => require!(
+            data.len() >= PULL_FEED_LEN && data.starts_with(&PULL_FEED_DISCRIMINATOR),
+            PredictionError::InvalidPriceUpdateV2
+        ),
+    }
+
+    Ok(source)
+}
+
+/// Reads the latest price from `oracle`, no older than `MAX_PRICE_AGE`.
+pub fn get_oracle_price(
+    source: OracleSource,
+    oracle: &AccountInfo,
+    clock: &Clock,
+) -> Result<OraclePrice> {
+    require!(
+        get_oracle_source(oracle)? == source,
+        PredictionError::InvalidPriceUpdateV2
+    );
+
+    let data = oracle.try_borrow_data()?;
+
+    match source {
+        OracleSource::Pyth => {
+            let price_update_v2 = PriceUpdateV2::try_deserialize(&mut &data[..])?;
+            let feed_id: FeedId = price_update_v2.price_message.feed_id;
+            let Price {
+                price,
+                exponent,
+                conf: _,
+                publish_time: _,
+            } = price_update_v2
+                .get_price_no_older_than(clock, MAX_PRICE_AGE, &feed_id)
+                .map_err(|err| match err {
+                    GetPriceError::PriceTooOld => error!(PredictionError::StalePrice),
+                    GetPriceError::MismatchedFeedId => error!(PredictionError::FeedIdMismatch),
+                    _ => error!(PredictionError::InvalidPriceUpdateV2),
+                })?;
+
+            Ok(OraclePrice {
+                price: price.into(),
+                exponent,
+            })
+        }
+        OracleSource::Switchboard => {
+            let last_update_timestamp = i64::from_le_bytes(
+                data[PULL_FEED_LAST_UPDATE_TIMESTAMP_OFFSET..][..8]
+                    .try_into()
+                    .map_err(|_| PredictionError::InvalidPriceUpdateV2)?,
+            );
+
+            require!(
+                clock.unix_timestamp - last_update_timestamp <= MAX_PRICE_AGE as i64,
+                PredictionError::StalePrice
+            );
+
+            Ok(OraclePrice {
+                price: i128::from_le_bytes(
+                    data[PULL_FEED_RESULT_VALUE_OFFSET..][..16]
+                        .try_into()
+                        .map_err(|_| PredictionError::InvalidPriceUpdateV2)?,
+                ),
+                exponent: SWITCHBOARD_EXPONENT,
+            })
+        }
+    }
+}
+
+/// Reads the market price from its primary oracle and any additional ones, in the order they were set.
+pub fn get_market_price(
+    market: &Market,
+    oracle: &AccountInfo,
+    extra_oracles: &[AccountInfo],
+    clock: &Clock,
+) -> Result<OraclePrice> {
+    require!(
+        extra_oracles.len() == usize::from(market.extra_oracle_count),
+        PredictionError::InvalidPriceUpdateV2
+    );
+
+    let mut prices = vec![get_oracle_price(market.oracle_source, oracle, clock)?];
+
+    for (i, extra_oracle) in extra_oracles.iter().enumerate() {
+        require_keys_eq!(
+            extra_oracle.key(),
+            market.extra_oracles[i],
+            PredictionError::InvalidPriceUpdateV2
+        );
+
+        prices.push(get_oracle_price(
+            market.extra_oracle_sources[i],
+            extra_oracle,
+            clock,
+        )?);
+    }
+
+    get_median_price(&prices, market.oracle_tolerance_bps)
+}
+
+/// Rebases `prices` to their lowest exponent, returning the scaled prices and that exponent.
+pub fn rebase_prices(prices: &[OraclePrice]) -> Result<(Vec<i128>, i32)> {
+    let exponent = prices.iter().map(|price| price.exponent).min().unwrap_or(0);
+    let rebased = prices
+        .iter()
+        .map(|price| {
+            10_i128
+                .checked_pow(price.exponent.abs_diff(exponent))
+                .and_then(|scale| price.price.checked_mul(scale))
+                .ok_or(error!(PredictionError::MathOverflow))
+        })
+        .collect::<Result<_>>()?;
+
+    Ok((rebased, exponent))
+}
+
+/// Median of `prices` rebased to their lowest exponent, rejecting spreads above `tolerance_bps`.
+pub fn get_median_price(prices: &[OraclePrice], tolerance_bps: u16) -> Result<OraclePrice> {
+    let (mut rebased, exponent) = rebase_prices(prices)?;
+
+    rebased.sort_unstable();
+
+    let mid = rebased.len() / 2;
+    let median = if rebased.len() % 2 == 0 {
+        rebased[mid - 1]
+            .checked_add(rebased[mid])
+            .ok_or(PredictionError::MathOverflow)?
+            / 2
+    } else {
+        rebased[mid]
+    };
+
+    let spread = rebased[rebased.len() - 1]
+        .checked_sub(rebased[0])
+        .ok_or(PredictionError::MathOverflow)?;
+
+    require!(
+        spread
+            .checked_mul(ONE_IN_BASIS_POINTS.into())
+            .ok_or(PredictionError::MathOverflow)?
+            <= median
+                .checked_abs()
+                .and_then(|median| median.checked_mul(tolerance_bps.into()))
+                .ok_or(PredictionError::MathOverflow)?,
+        PredictionError::OraclePriceDivergence
+    );
+
+    Ok(OraclePrice {
+        price: median,
+        exponent,
+    })
+}
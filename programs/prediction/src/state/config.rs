@@ -3,6 +3,32 @@ use anchor_lang::prelude::*;
 #[account]
 #[derive(InitSpace)]
 pub struct Config {
+    pub version: u8,
+    pub authority: Pubkey,
+    pub fee_bps: u16,
+    pub mint: Pubkey,
+    pub share_price: u64,
+    pub bump: u8,
+    /// Shortest allowed time between market creation and `resolve_ts`
+    pub min_market_duration: i64,
+    /// Longest allowed time between market creation and `resolve_ts`
+    pub max_market_duration: i64,
+    /// Number of markets created, used as the next market id
+    pub market_count: u64,
+    /// Number of market series created, used as the next series id
+    pub series_count: u64,
+    /// Number of market templates created, used as the next template id
+    pub template_count: u64,
+    /// Trading mint amount posted by proposers and challengers of optimistic resolutions
+    pub proposal_bond: u64,
+    /// Time after a proposal during which it can be disputed
+    pub dispute_window: i64,
+    pub reserved: [u8; 72],
+}
+
+/// Unversioned layout of config accounts created before `version` was introduced.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ConfigV0 {
     pub authority: Pubkey,
     pub fee_bps: u16,
     pub mint: Pubkey,
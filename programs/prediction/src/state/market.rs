@@ -1,24 +1,443 @@
 use anchor_lang::prelude::*;
 use num_derive::{FromPrimitive, ToPrimitive};
 
+use crate::{
+    error::PredictionError,
+    oracle::OraclePrice,
+    utils::{calculate_price, split_fee, to_u64},
+    MARKET_VERSION, MAX_EXTRA_ORACLES, MAX_OUTCOMES, ONE_IN_BASIS_POINTS,
+};
+
 #[account]
 pub struct Market {
-    pub resolve_ts: i64,         // 8
-    pub pass_shares: u64,        // 8
-    pub fail_shares: u64,        // 8
-    pub state: MarketState,      // 1
-    pub price_update_v2: Pubkey, // 32
-    pub target_price: f64,       // 8
-    pub bump: u8,                // 1
-    pub title: String,           // 4
+    pub version: u8,                             // 1
+    pub resolve_ts: i64,                         // 8
+    pub pass_shares: u64,                        // 8
+    pub fail_shares: u64,                        // 8
+    pub state: MarketState,                      // 1
+    pub price_update_v2: Pubkey,                 // 32
+    pub target_price: f64,                       // 8
+    pub share_price: u64,                        // 8
+    pub volume: u64,                             // 8
+    pub fees_collected: u64,                     // 8
+    pub trade_count: u64,                        // 8
+    pub unique_traders: u64,                     // 8
+    pub last_trade_ts: i64,                      // 8
+    pub bump: u8,                                // 1
+    pub id: u64,                                 // 8
+    pub series: Pubkey,                          // 32
+    pub condition: PriceCondition,               // 1
+    pub has_fee_override: bool,                  // 1
+    pub fee_bps: u16,                            // 2
+    pub template: Pubkey,                        // 32
+    pub settled_price: f64,                      // 8
+    pub kind: MarketKind,                        // 1
+    pub outcome_count: u8,                       // 1
+    pub outcome_shares: [u64; 8],                // 64
+    pub bounds: [f64; 7],                        // 56
+    pub winning_outcome: u8,                     // 1
+    pub resolution: ResolutionKind,              // 1
+    pub resolver: Pubkey,                        // 32
+    pub oracle_source: OracleSource,             // 1
+    pub extra_oracle_count: u8,                  // 1
+    pub extra_oracles: [Pubkey; 2],              // 64
+    pub extra_oracle_sources: [OracleSource; 2], // 2
+    pub oracle_tolerance_bps: u16,               // 2
+    pub twap_window: i64,                        // 8
+    pub total_collateral: u64,                   // 8
+    pub outcome_collateral: [u64; 8],            // 64
+    pub seeds: MarketSeeds,                      // 1
+    pub reserved: [u8; 121],                     // 121
+    pub title: String,                           // 4
 }
 
 impl Market {
+    pub fn new(
+        id: u64,
+        resolve_ts: i64,
+        price_update_v2: Pubkey,
+        target_price: f64,
+        share_price: u64,
+        bump: u8,
+        title: String,
+    ) -> Self {
+        Market {
+            version: MARKET_VERSION,
+            id,
+            resolve_ts,
+            pass_shares: 0,
+            fail_shares: 0,
+            state: MarketState::Initialized,
+            price_update_v2,
+            target_price,
+            share_price,
+            volume: 0,
+            fees_collected: 0,
+            trade_count: 0,
+            unique_traders: 0,
+            last_trade_ts: 0,
+            bump,
+            series: Pubkey::default(),
+            condition: PriceCondition::Above,
+            has_fee_override: false,
+            fee_bps: 0,
+            template: Pubkey::default(),
+            settled_price: 0.0,
+            kind: MarketKind::Binary,
+            outcome_count: 0,
+            outcome_shares: [0; MAX_OUTCOMES],
+            bounds: [0.0; MAX_OUTCOMES - 1],
+            winning_outcome: 0,
+            resolution: ResolutionKind::Oracle,
+            resolver: Pubkey::default(),
+            oracle_source: OracleSource::Pyth,
+            extra_oracle_count: 0,
+            extra_oracles: [Pubkey::default(); MAX_EXTRA_ORACLES],
+            extra_oracle_sources: [OracleSource::Pyth; MAX_EXTRA_ORACLES],
+            oracle_tolerance_bps: 0,
+            twap_window: 0,
+            total_collateral: 0,
+            outcome_collateral: [0; MAX_OUTCOMES],
+            seeds: MarketSeeds::Id,
+            reserved: [0; 121],
+            title,
+        }
+    }
+
+    /// Seeds following `MARKET_SEED` in the market address, the second one empty for id seeds.
+    pub fn address_seeds(&self) -> [Vec<u8>; 2] {
+        match self.seeds {
+            MarketSeeds::Id => [self.id.to_le_bytes().to_vec(), Vec::new()],
+            MarketSeeds::Legacy => [
+                self.price_update_v2.to_bytes().to_vec(),
+                self.resolve_ts.to_le_bytes().to_vec(),
+            ],
+        }
+    }
+
+    /// Outcome whose bucket contains `price`, where outcome `i` covers `[bounds[i - 1], bounds[i])`.
+    pub fn outcome_for_price(&self, price: f64) -> u8 {
+        self.bounds[..usize::from(self.outcome_count) - 1]
+            .iter()
+            .take_while(|&&bound| price >= bound)
+            .count() as u8
+    }
+
+    /// Checks `outcome` can settle the market: 0 for PASS and 1 for FAIL, or a categorical bucket.
+    pub fn validate_outcome(&self, outcome: u8) -> Result<()> {
+        match self.kind {
+            MarketKind::Binary => require!(outcome <= 1, PredictionError::InvalidOutcome),
+            MarketKind::Categorical => require!(
+                outcome < self.outcome_count,
+                PredictionError::InvalidOutcome
+            ),
+            MarketKind::Scalar => return err!(PredictionError::InvalidMarketKind),
+        }
+
+        Ok(())
+    }
+
+    /// Settles the market to an outcome accepted by `validate_outcome`.
+    pub fn settle_outcome(&mut self, outcome: u8) -> Result<()> {
+        self.validate_outcome(outcome)?;
+
+        self.state = match self.kind {
+            MarketKind::Categorical => {
+                self.winning_outcome = outcome;
+                MarketState::Resolved
+            }
+            _ if outcome == 0 => MarketState::Passed,
+            _ => MarketState::Failed,
+        };
+
+        Ok(())
+    }
+
+    /// Checks a proposed settlement: a finite settled price for scalar markets, whose outcome is
+    /// ignored, or an outcome accepted by `validate_outcome` for the others.
+    pub fn validate_proposal(&self, outcome: u8, settled_price: Option<f64>) -> Result<()> {
+        match (self.kind, settled_price) {
+            (MarketKind::Scalar, Some(price)) => {
+                require!(price.is_finite(), PredictionError::InvalidOutcome)
+            }
+            (MarketKind::Scalar, None) | (_, Some(_)) => {
+                return err!(PredictionError::InvalidOutcome)
+            }
+            (_, None) => self.validate_outcome(outcome)?,
+        }
+
+        Ok(())
+    }
+
+    /// Settles the market to a proposal accepted by `validate_proposal`.
+    pub fn settle_proposal(&mut self, outcome: u8, settled_price: Option<f64>) -> Result<()> {
+        self.validate_proposal(outcome, settled_price)?;
+
+        match settled_price {
+            Some(price) => {
+                self.settled_price = price;
+                self.state = MarketState::Resolved;
+            }
+            None => self.settle_outcome(outcome)?,
+        }
+
+        Ok(())
+    }
+
+    /// Settles the market from an oracle price: binary markets pass or fail against the target,
+    /// categorical markets resolve to the bucket containing the price and scalar markets resolve.
+    pub fn settle_price(&mut self, price: &OraclePrice) {
+        self.settled_price = price.to_f64();
+        self.state = match self.kind {
+            MarketKind::Binary => {
+                let is_above = self.settled_price >= self.target_price;
+
+                if is_above == (self.condition == PriceCondition::Above) {
+                    MarketState::Passed
+                } else {
+                    MarketState::Failed
+                }
+            }
+            MarketKind::Categorical => {
+                self.winning_outcome = self.outcome_for_price(self.settled_price);
+                MarketState::Resolved
+            }
+            MarketKind::Scalar => MarketState::Resolved,
+        };
+    }
+
+    /// Implied probability of PASS in basis points, by PASS's share of all shares outstanding.
+    pub fn pass_probability_bps(&self) -> u16 {
+        let total_shares = u128::from(self.pass_shares) + u128::from(self.fail_shares);
+
+        if total_shares == 0 {
+            return ONE_IN_BASIS_POINTS / 2;
+        }
+
+        (u128::from(self.pass_shares) * u128::from(ONE_IN_BASIS_POINTS) / total_shares) as u16
+    }
+
+    /// Price of the next PASS or FAIL share, excluding fees.
+    pub fn marginal_price(&self, is_pass: bool) -> Result<u64> {
+        let (pass_shares, fail_shares) = if is_pass {
+            (self.pass_shares.checked_add(1), Some(self.fail_shares))
+        } else {
+            (Some(self.pass_shares), self.fail_shares.checked_add(1))
+        };
+
+        let (_, price) = calculate_price(
+            1,
+            pass_shares.ok_or(PredictionError::MathOverflow)?,
+            fail_shares.ok_or(PredictionError::MathOverflow)?,
+            is_pass,
+            0,
+            self.share_price,
+        )?;
+
+        Ok(price)
+    }
+
+    /// Marginal prices of PASS and FAIL.
+    pub fn spot_prices(&self) -> Result<(u64, u64)> {
+        Ok((self.marginal_price(true)?, self.marginal_price(false)?))
+    }
+
+    /// Fee charged on trades, unless overridden for this market.
+    pub fn trade_fee_bps(&self, config_fee_bps: u16) -> u16 {
+        if self.has_fee_override {
+            self.fee_bps
+        } else {
+            config_fee_bps
+        }
+    }
+
+    /// Shares outstanding across all outcomes, PASS/LONG and FAIL/SHORT for binary and scalar
+    /// markets or each bucket for categorical ones.
+    pub fn open_interest(&self) -> Result<u64> {
+        self.outcome_shares
+            .iter()
+            .try_fold(self.pass_shares, |total, &shares| total.checked_add(shares))
+            .and_then(|total| total.checked_add(self.fail_shares))
+            .ok_or(PredictionError::MathOverflow.into())
+    }
+
+    /// Whether the market has been traded, counting shares and collateral outstanding for legacy
+    /// markets migrated without a trade count.
+    pub fn has_traded(&self) -> bool {
+        self.trade_count > 0
+            || self.pass_shares > 0
+            || self.fail_shares > 0
+            || self.outcome_shares.iter().any(|&shares| shares > 0)
+            || self.total_collateral > 0
+    }
+
+    /// Buys or sells PASS/LONG or FAIL/SHORT shares, returning the fee and net price of the trade.
+    pub fn trade_shares(
+        &mut self,
+        shares: u64,
+        is_pass: bool,
+        is_buy: bool,
+        fee_bps: u16,
+    ) -> Result<(u64, u64)> {
+        let market_shares = if is_pass {
+            &mut self.pass_shares
+        } else {
+            &mut self.fail_shares
+        };
+
+        *market_shares = if is_buy {
+            market_shares
+                .checked_add(shares)
+                .ok_or(PredictionError::MathOverflow)?
+        } else {
+            market_shares
+                .checked_sub(shares)
+                .ok_or(PredictionError::InsufficientSharesToSell)?
+        };
+
+        let outcome_shares = *market_shares;
+        let quote = calculate_price(
+            shares,
+            self.pass_shares,
+            self.fail_shares,
+            is_pass,
+            fee_bps,
+            self.share_price,
+        )?;
+
+        let outcome = if is_pass { 0 } else { 1 };
+
+        if is_buy {
+            self.add_collateral(outcome, quote.1)?;
+
+            Ok(quote)
+        } else {
+            self.withdraw_collateral(outcome, shares, outcome_shares + shares, quote, fee_bps)
+        }
+    }
+
+    /// Records `amount` paid into the market for `outcome` shares, where binary and scalar markets
+    /// use outcome 0 for PASS/LONG and 1 for FAIL/SHORT.
+    pub fn add_collateral(&mut self, outcome: usize, amount: u64) -> Result<()> {
+        self.outcome_collateral[outcome] = self.outcome_collateral[outcome]
+            .checked_add(amount)
+            .ok_or(PredictionError::MathOverflow)?;
+        self.total_collateral = self
+            .total_collateral
+            .checked_add(amount)
+            .ok_or(PredictionError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Withdraws the fee and net price of selling `shares` out of the `outcome_shares` held before
+    /// the sale, capped at their pro-rata claim on the outcome's collateral so a sale never lowers
+    /// the collateral backing each remaining share.
+    pub fn withdraw_collateral(
+        &mut self,
+        outcome: usize,
+        shares: u64,
+        outcome_shares: u64,
+        (fee, amount): (u64, u64),
+        fee_bps: u16,
+    ) -> Result<(u64, u64)> {
+        let claim = to_u64(
+            u128::from(self.outcome_collateral[outcome])
+                .checked_mul(shares.into())
+                .ok_or(PredictionError::MathOverflow)?
+                .checked_div(outcome_shares.into())
+                .ok_or(PredictionError::DivisionByZero)?,
+        )?;
+
+        let (fee, amount) = if fee
+            .checked_add(amount)
+            .ok_or(PredictionError::MathOverflow)?
+            > claim
+        {
+            split_fee(claim, fee_bps)?
+        } else {
+            (fee, amount)
+        };
+
+        let withdrawn = fee + amount;
+
+        self.outcome_collateral[outcome] = self.outcome_collateral[outcome]
+            .checked_sub(withdrawn)
+            .ok_or(PredictionError::InsolventMarket)?;
+        self.total_collateral = self
+            .total_collateral
+            .checked_sub(withdrawn)
+            .ok_or(PredictionError::InsolventMarket)?;
+
+        Ok((fee, amount))
+    }
+
+    /// Basis points of the pot owed to LONG shares, by where the settled price fell in the range.
+    pub fn scalar_long_bps(&self) -> u16 {
+        let (lower, upper) = (self.bounds[0], self.bounds[1]);
+        let fraction = ((self.settled_price - lower) / (upper - lower)).clamp(0.0, 1.0);
+
+        (fraction * f64::from(ONE_IN_BASIS_POINTS)) as u16
+    }
+
     pub fn space(title: String) -> usize {
-        return Market::DISCRIMINATOR.len() + 8 + 8 + 8 + 1 + 32 + 8 + 1 + 4 + title.len();
+        Market::DISCRIMINATOR.len()
+            + 1
+            + 8
+            + 8
+            + 8
+            + 1
+            + 32
+            + 8
+            + 8
+            + 8
+            + 8
+            + 8
+            + 8
+            + 8
+            + 1
+            + 8
+            + 32
+            + 1
+            + 1
+            + 2
+            + 32
+            + 8
+            + 1
+            + 1
+            + 64
+            + 56
+            + 1
+            + 1
+            + 32
+            + 1
+            + 1
+            + 64
+            + 2
+            + 2
+            + 8
+            + 8
+            + 64
+            + 1
+            + 121
+            + 4
+            + title.len()
     }
 }
 
+/// Unversioned layout of market accounts created before `version` was introduced.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct MarketV0 {
+    pub resolve_ts: i64,
+    pub pass_shares: u64,
+    pub fail_shares: u64,
+    pub state: MarketState,
+    pub price_update_v2: Pubkey,
+    pub target_price: f64,
+    pub bump: u8,
+    pub title: String,
+}
+
 #[derive(
     AnchorSerialize,
     AnchorDeserialize,
@@ -35,4 +454,427 @@ pub enum MarketState {
     Passed,
     Failed,
     Undecided,
+    /// Categorical market settled with `winning_outcome`, or scalar market settled with `settled_price`
+    Resolved,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, InitSpace)]
+pub enum PriceCondition {
+    /// Passes when the settled price is at or above target
+    Above,
+    /// Passes when the settled price is below target
+    Below,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, InitSpace)]
+pub enum MarketKind {
+    /// PASS/FAIL shares on a single target price
+    Binary,
+    /// One outcome per price bucket delimited by `bounds`
+    Categorical,
+    /// LONG (pass) and SHORT (fail) shares paid linearly between `bounds[0]` and `bounds[1]`
+    Scalar,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, InitSpace)]
+pub enum ResolutionKind {
+    /// Settled from `price_update_v2` through `settle_market`
+    Oracle,
+    /// Settled by `resolver` through `resolve_market_manual`
+    Manual,
+    /// Settled by a bonded proposal through `propose_outcome` and `finalize_outcome`
+    Optimistic,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, InitSpace)]
+pub enum OracleSource {
+    /// `price_update_v2` is a Pyth `PriceUpdateV2` account
+    Pyth,
+    /// `price_update_v2` is a Switchboard on-demand pull feed
+    Switchboard,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, InitSpace)]
+pub enum MarketSeeds {
+    /// `[MARKET_SEED, id]`
+    Id,
+    /// `[MARKET_SEED, price_update_v2, resolve_ts]`, kept by markets migrated from the legacy layout
+    Legacy,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FEE_BPS: u16 = 10;
+
+    fn binary_market(target_price: f64, condition: PriceCondition) -> Market {
+        Market {
+            condition,
+            ..Market::new(
+                0,
+                0,
+                Pubkey::default(),
+                target_price,
+                1_000_000,
+                255,
+                String::new(),
+            )
+        }
+    }
+
+    fn settle(target_price: f64, condition: PriceCondition, price: i128, exponent: i32) -> Market {
+        let mut market = binary_market(target_price, condition);
+
+        market.settle_price(&OraclePrice { price, exponent });
+
+        market
+    }
+
+    #[test]
+    fn settles_above_condition_across_exponents() {
+        let cases = [
+            // (price, exponent, target, expected)
+            (19_114_841_996, -8, 150.0, MarketState::Passed),
+            (15_000_000_000, -8, 150.0, MarketState::Passed),
+            (14_999_999_999, -8, 150.0, MarketState::Failed),
+            (19_114_841_996, -8, 191.14841996, MarketState::Passed),
+            (19_114_841_995, -8, 191.14841996, MarketState::Failed),
+            (150_000, -3, 150.0, MarketState::Passed),
+            (149_999, -3, 150.0, MarketState::Failed),
+            (150, 0, 150.0, MarketState::Passed),
+            (149, 0, 150.0, MarketState::Failed),
+            (151, 0, 150.0, MarketState::Passed),
+            (15, 1, 150.0, MarketState::Passed),
+            (14, 1, 150.0, MarketState::Failed),
+            (2, 2, 150.0, MarketState::Passed),
+            (1, 2, 150.0, MarketState::Failed),
+        ];
+
+        for (price, exponent, target_price, expected) in cases {
+            let market = settle(target_price, PriceCondition::Above, price, exponent);
+
+            assert!(
+                market.state == expected,
+                "price {price}e{exponent} against target {target_price}"
+            );
+        }
+    }
+
+    #[test]
+    fn settles_below_condition_as_complement_of_above() {
+        for (price, exponent) in [
+            (14_999_999_999, -8),
+            (15_000_000_000, -8),
+            (15, 1),
+            (149, 0),
+        ] {
+            let above = settle(150.0, PriceCondition::Above, price, exponent);
+            let below = settle(150.0, PriceCondition::Below, price, exponent);
+
+            assert!(above.state != below.state);
+            assert!(matches!(
+                below.state,
+                MarketState::Passed | MarketState::Failed
+            ));
+        }
+    }
+
+    #[test]
+    fn settled_price_matches_oracle_price() {
+        let market = settle(150.0, PriceCondition::Above, 19_114_841_996, -8);
+
+        assert_eq!(market.settled_price, 191.14841996);
+
+        let market = settle(150.0, PriceCondition::Above, 42, 3);
+
+        assert_eq!(market.settled_price, 42_000.0);
+    }
+
+    #[test]
+    fn settles_categorical_market_to_bucket_containing_price() {
+        let mut market = Market {
+            kind: MarketKind::Categorical,
+            outcome_count: 3,
+            bounds: [150.0, 200.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+            ..binary_market(0.0, PriceCondition::Above)
+        };
+
+        for (price, outcome) in [
+            (14_999, -2, 0),
+            (15_000, -2, 1),
+            (19_114_841_996, -8, 1),
+            (2, 2, 2),
+        ]
+        .map(|(price, exponent, outcome)| (OraclePrice { price, exponent }, outcome))
+        {
+            market.settle_price(&price);
+
+            assert!(market.state == MarketState::Resolved);
+            assert_eq!(market.winning_outcome, outcome);
+        }
+    }
+
+    #[test]
+    fn settles_scalar_proposal_to_settled_price() {
+        let mut market = Market {
+            kind: MarketKind::Scalar,
+            bounds: [100.0, 200.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+            state: MarketState::Undecided,
+            ..binary_market(0.0, PriceCondition::Above)
+        };
+
+        assert!(market.validate_proposal(0, None).is_err());
+        assert!(market.validate_proposal(0, Some(f64::NAN)).is_err());
+
+        market.settle_proposal(0, Some(175.0)).unwrap();
+
+        assert!(market.state == MarketState::Resolved);
+        assert_eq!(market.settled_price, 175.0);
+        assert_eq!(market.scalar_long_bps(), 7_500);
+    }
+
+    #[test]
+    fn rejects_settled_price_proposal_for_outcome_markets() {
+        let mut market = binary_market(150.0, PriceCondition::Above);
+
+        assert!(market.validate_proposal(0, Some(175.0)).is_err());
+
+        market.settle_proposal(1, None).unwrap();
+
+        assert!(market.state == MarketState::Failed);
+    }
+
+    /// Trades through `Market::trade_shares`, moving collateral in and out of `balance` as the
+    /// market token account would.
+    fn trade(market: &mut Market, balance: &mut u64, is_pass: bool, shares: u64, is_buy: bool) {
+        let (fee, amount) = market
+            .trade_shares(shares, is_pass, is_buy, FEE_BPS)
+            .unwrap();
+
+        if is_buy {
+            *balance += amount;
+        } else {
+            *balance -= fee + amount;
+        }
+
+        assert!(*balance >= market.total_collateral);
+        assert_eq!(
+            market.total_collateral,
+            market.outcome_collateral.iter().sum::<u64>()
+        );
+    }
+
+    /// Payout to each holder if `holdings` win, split pro rata of the market collateral.
+    fn payouts(market: &Market, holdings: &[u64], winning_shares: u64) -> Vec<u64> {
+        holdings
+            .iter()
+            .map(|&shares| {
+                (u128::from(market.total_collateral) * u128::from(shares)
+                    / u128::from(winning_shares)) as u64
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sale_after_cheap_opposing_buy_cannot_drain_opposing_collateral() {
+        let mut market = binary_market(150.0, PriceCondition::Above);
+        let mut balance = 0;
+
+        trade(&mut market, &mut balance, true, 10, true);
+        trade(&mut market, &mut balance, false, 1, true);
+
+        let fail_collateral = market.outcome_collateral[1];
+        let pass_collateral = market.outcome_collateral[0];
+
+        // the quote for 10 PASS against 1 FAIL is close to twice what was paid for them
+        trade(&mut market, &mut balance, true, 10, false);
+
+        assert_eq!(market.outcome_collateral[1], fail_collateral);
+        assert_eq!(market.outcome_collateral[0], 0);
+        assert_eq!(balance, fail_collateral);
+        assert!(pass_collateral > 0);
+
+        // the FAIL holder is paid everything left in the market
+        assert_eq!(payouts(&market, &[1], market.fail_shares), [balance]);
+    }
+
+    #[test]
+    fn no_trade_sequence_leaves_winners_underfunded() {
+        const TRADERS: usize = 4;
+
+        // deterministic linear congruential generator so failures are reproducible
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = |bound: u64| {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (seed >> 33) % bound
+        };
+
+        for _ in 0..200 {
+            let mut market = binary_market(150.0, PriceCondition::Above);
+            let mut balance = 0;
+            let mut holdings = [[0_u64; 2]; TRADERS];
+
+            for _ in 0..60 {
+                let trader = next(TRADERS as u64) as usize;
+                let side = next(2) as usize;
+                let is_buy = next(3) != 0;
+                let shares = next(25) + 1;
+
+                if !is_buy && holdings[trader][side] < shares {
+                    continue;
+                }
+
+                let outcome_shares = [market.pass_shares, market.fail_shares][side];
+                let outcome_collateral = market.outcome_collateral[side];
+
+                trade(&mut market, &mut balance, side == 0, shares, is_buy);
+
+                if is_buy {
+                    holdings[trader][side] += shares;
+                } else {
+                    holdings[trader][side] -= shares;
+
+                    // selling never lowers the collateral backing each remaining share
+                    let remaining = [market.pass_shares, market.fail_shares][side];
+
+                    assert!(
+                        u128::from(market.outcome_collateral[side]) * u128::from(outcome_shares)
+                            >= u128::from(outcome_collateral) * u128::from(remaining)
+                    );
+                }
+            }
+
+            for (side, winning_shares) in [market.pass_shares, market.fail_shares]
+                .into_iter()
+                .enumerate()
+            {
+                if winning_shares == 0 {
+                    continue;
+                }
+
+                let winners = holdings.map(|holding| holding[side]);
+                let paid = payouts(&market, &winners, winning_shares)
+                    .iter()
+                    .sum::<u64>();
+
+                // every winner is paid from collateral the market holds, and winners recover at
+                // least the collateral their side put in, less rounding
+                assert!(paid <= balance);
+                assert!(paid + TRADERS as u64 >= market.outcome_collateral[side]);
+            }
+        }
+    }
+
+    fn market_with_shares(pass_shares: u64, fail_shares: u64) -> Market {
+        Market {
+            pass_shares,
+            fail_shares,
+            ..binary_market(150.0, PriceCondition::Above)
+        }
+    }
+
+    #[test]
+    fn pass_probability_follows_share_ratio() {
+        assert_eq!(market_with_shares(0, 0).pass_probability_bps(), 5_000);
+        assert_eq!(market_with_shares(10, 0).pass_probability_bps(), 10_000);
+        assert_eq!(market_with_shares(0, 10).pass_probability_bps(), 0);
+        assert_eq!(market_with_shares(10, 10).pass_probability_bps(), 5_000);
+        assert_eq!(market_with_shares(30, 10).pass_probability_bps(), 7_500);
+        assert_eq!(market_with_shares(1, 2).pass_probability_bps(), 3_333);
+    }
+
+    #[test]
+    fn marginal_price_is_flat_until_both_sides_trade() {
+        for (pass_shares, fail_shares) in [(0, 0), (10, 0), (0, 10)] {
+            let market = market_with_shares(pass_shares, fail_shares);
+
+            // the side without shares becomes two-sided once its next share is bought
+            let expected_pass = if fail_shares == 0 {
+                1_000_000
+            } else {
+                2_000_000 / (fail_shares + 1)
+            };
+            let expected_fail = if pass_shares == 0 {
+                1_000_000
+            } else {
+                2_000_000 / (pass_shares + 1)
+            };
+
+            assert_eq!(market.marginal_price(true).unwrap(), expected_pass);
+            assert_eq!(market.marginal_price(false).unwrap(), expected_fail);
+        }
+    }
+
+    #[test]
+    fn marginal_price_scales_with_share_of_outcomes() {
+        // next share priced at 2 * outcome shares / total shares of the base share price
+        let market = market_with_shares(10, 5);
+
+        assert_eq!(market.marginal_price(true).unwrap(), 1_375_000);
+        assert_eq!(market.marginal_price(false).unwrap(), 750_000);
+        assert_eq!(market.spot_prices().unwrap(), (1_375_000, 750_000));
+
+        let market = market_with_shares(10, 10);
+
+        assert_eq!(market.spot_prices().unwrap(), (1_047_619, 1_047_619));
+    }
+
+    #[test]
+    fn marginal_price_matches_single_share_buy() {
+        let mut market = market_with_shares(7, 3);
+        let marginal_price = market.marginal_price(false).unwrap();
+        let (fee, amount) = market.trade_shares(1, false, true, 0).unwrap();
+
+        assert_eq!(fee, 0);
+        assert_eq!(amount, marginal_price);
+    }
+
+    #[test]
+    fn migrated_market_with_shares_counts_as_traded() {
+        let market = binary_market(150.0, PriceCondition::Above);
+
+        assert!(!market.has_traded());
+
+        // legacy markets are migrated with shares and collateral but no trade count
+        let migrated = Market {
+            pass_shares: 30,
+            fail_shares: 10,
+            total_collateral: 12_000_000,
+            ..binary_market(150.0, PriceCondition::Above)
+        };
+
+        assert_eq!(migrated.trade_count, 0);
+        assert!(migrated.has_traded());
+    }
+
+    #[test]
+    fn open_interest_sums_shares_outstanding() {
+        let binary = Market {
+            pass_shares: 30,
+            fail_shares: 10,
+            ..binary_market(150.0, PriceCondition::Above)
+        };
+
+        assert_eq!(binary.open_interest().unwrap(), 40);
+
+        let categorical = Market {
+            kind: MarketKind::Categorical,
+            outcome_count: 3,
+            outcome_shares: [5, 0, 7, 0, 0, 0, 0, 0],
+            ..binary_market(0.0, PriceCondition::Above)
+        };
+
+        assert_eq!(categorical.open_interest().unwrap(), 12);
+
+        let overflowing = Market {
+            pass_shares: u64::MAX,
+            fail_shares: 1,
+            ..binary
+        };
+
+        assert!(overflowing.open_interest().is_err());
+    }
 }
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct MarketMetadata {
+    pub version: u8,              // 1
+    pub market: Pubkey,           // 32
+    pub category: MarketCategory, // 1
+    pub bump: u8,                 // 1
+    pub reserved: [u8; 64],       // 64
+    pub description: String,      // 4
+    pub tags: Vec<String>,        // 4
+    pub uri: String,              // 4
+}
+
+impl MarketMetadata {
+    pub fn space(description: &str, tags: &[String], uri: &str) -> usize {
+        MarketMetadata::DISCRIMINATOR.len()
+            + 1
+            + 32
+            + 1
+            + 1
+            + 64
+            + 4
+            + description.len()
+            + 4
+            + tags.iter().map(|tag| 4 + tag.len()).sum::<usize>()
+            + 4
+            + uri.len()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, InitSpace)]
+pub enum MarketCategory {
+    Crypto,
+    Fx,
+    Commodities,
+    Other,
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct MarketSeries {
+    pub version: u8,             // 1
+    pub id: u64,                 // 8
+    pub price_update_v2: Pubkey, // 32
+    pub resolve_ts: i64,         // 8
+    pub bump: u8,                // 1
+    pub reserved: [u8; 64],      // 64
+    pub markets: Vec<Pubkey>,    // 4
+}
+
+impl MarketSeries {
+    pub fn space(market_count: usize) -> usize {
+        MarketSeries::DISCRIMINATOR.len() + 1 + 8 + 32 + 8 + 1 + 64 + 4 + 32 * market_count
+    }
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::PriceCondition;
+
+#[account]
+pub struct MarketTemplate {
+    pub version: u8,               // 1
+    pub id: u64,                   // 8
+    pub price_update_v2: Pubkey,   // 32
+    pub condition: PriceCondition, // 1
+    pub duration: i64,             // 8
+    pub has_fee_override: bool,    // 1
+    pub fee_bps: u16,              // 2
+    pub strike_rule: StrikeRule,   // 1
+    pub target_price: f64,         // 8
+    pub offset_bps: i16,           // 2
+    pub last_market: Pubkey,       // 32
+    pub spawn_count: u64,          // 8
+    pub bump: u8,                  // 1
+    pub reserved: [u8; 64],        // 64
+    pub title: String,             // 4
+}
+
+impl MarketTemplate {
+    pub fn space(title: &str) -> usize {
+        MarketTemplate::DISCRIMINATOR.len()
+            + 1
+            + 8
+            + 32
+            + 1
+            + 8
+            + 1
+            + 2
+            + 1
+            + 8
+            + 2
+            + 32
+            + 8
+            + 1
+            + 64
+            + 4
+            + title.len()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, InitSpace)]
+pub enum StrikeRule {
+    /// Every market uses the template target price
+    Fixed,
+    /// Target is the previous market's settled price shifted by `offset_bps`,
+    /// falling back to the template target price when there is none
+    LastSettlementOffset,
+}
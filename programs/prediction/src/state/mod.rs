@@ -1,7 +1,17 @@
 pub mod config;
 pub mod market;
+pub mod market_metadata;
+pub mod market_series;
+pub mod market_template;
 pub mod position;
+pub mod price_samples;
+pub mod resolution;
 
 pub use config::*;
 pub use market::*;
+pub use market_metadata::*;
+pub use market_series::*;
+pub use market_template::*;
 pub use position::*;
+pub use price_samples::*;
+pub use resolution::*;
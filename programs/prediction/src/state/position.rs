@@ -3,6 +3,29 @@ use anchor_lang::prelude::*;
 #[account]
 #[derive(InitSpace)]
 pub struct Position {
+    pub version: u8,
+    pub authority: Pubkey,
+    pub market: Pubkey,
+    pub pass_shares: u64,
+    pub fail_shares: u64,
+    /// Collateral spent on PASS shares still held, including fees
+    pub pass_cost_basis: u64,
+    /// Collateral spent on FAIL shares still held, including fees
+    pub fail_cost_basis: u64,
+    /// Collateral received minus cost basis released, net of fees
+    pub realized_pnl: i64,
+    pub total_fees_paid: u64,
+    pub bump: u8,
+    /// Shares held per outcome of a categorical market
+    pub outcome_shares: [u64; 8],
+    /// Collateral spent per outcome of a categorical market still held, including fees
+    pub outcome_cost_basis: [u64; 8],
+    pub reserved: [u8; 128],
+}
+
+/// Unversioned layout of position accounts created before `version` was introduced.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PositionV0 {
     pub authority: Pubkey,
     pub market: Pubkey,
     pub pass_shares: u64,
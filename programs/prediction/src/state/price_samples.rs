@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PredictionError,
+    oracle::{rebase_prices, OraclePrice},
+    MAX_PRICE_SAMPLES,
+};
+
+#[account]
+#[derive(InitSpace)]
+pub struct PriceSamples {
+    pub version: u8,
+    pub market: Pubkey,
+    /// Index the next sample is written to, overwriting the oldest once full
+    pub head: u8,
+    pub count: u8,
+    pub bump: u8,
+    pub samples: [PriceSample; 32],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Default, InitSpace)]
+pub struct PriceSample {
+    pub price: i128,
+    pub exponent: i32,
+    pub timestamp: i64,
+}
+
+impl PriceSamples {
+    pub fn last(&self) -> Option<&PriceSample> {
+        (self.count > 0).then(|| {
+            &self.samples[(usize::from(self.head) + MAX_PRICE_SAMPLES - 1) % MAX_PRICE_SAMPLES]
+        })
+    }
+
+    pub fn push(&mut self, sample: PriceSample) {
+        self.samples[usize::from(self.head)] = sample;
+        self.head = ((usize::from(self.head) + 1) % MAX_PRICE_SAMPLES) as u8;
+        self.count = (usize::from(self.count) + 1).min(MAX_PRICE_SAMPLES) as u8;
+    }
+
+    /// Samples from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = &PriceSample> {
+        let start = usize::from(self.head) + MAX_PRICE_SAMPLES - usize::from(self.count);
+
+        (start..start + usize::from(self.count)).map(|i| &self.samples[i % MAX_PRICE_SAMPLES])
+    }
+
+    /// Average of the samples, each weighted by how long it held until the next one or `end_ts`.
+    pub fn twap(&self, end_ts: i64) -> Result<Option<OraclePrice>> {
+        let Some(last) = self.last() else {
+            return Ok(None);
+        };
+        let samples = self.iter().collect::<Vec<_>>();
+        let (prices, exponent) = rebase_prices(
+            &samples
+                .iter()
+                .map(|sample| OraclePrice {
+                    price: sample.price,
+                    exponent: sample.exponent,
+                })
+                .collect::<Vec<_>>(),
+        )?;
+
+        let mut weighted_sum = 0_i128;
+        let mut total_weight = 0_i128;
+
+        for (i, price) in prices.iter().enumerate() {
+            let next_ts = samples.get(i + 1).map_or(end_ts, |sample| sample.timestamp);
+            let weight = i128::from(next_ts - samples[i].timestamp);
+
+            weighted_sum = weighted_sum
+                .checked_add(
+                    price
+                        .checked_mul(weight)
+                        .ok_or(PredictionError::MathOverflow)?,
+                )
+                .ok_or(PredictionError::MathOverflow)?;
+            total_weight += weight;
+        }
+
+        // a single sample taken at `end_ts` carries no weight
+        if total_weight == 0 {
+            return Ok(Some(OraclePrice {
+                price: last.price,
+                exponent: last.exponent,
+            }));
+        }
+
+        Ok(Some(OraclePrice {
+            price: weighted_sum / total_weight,
+            exponent,
+        }))
+    }
+}
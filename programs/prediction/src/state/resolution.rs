@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Resolution {
+    pub version: u8,
+    pub market: Pubkey,
+    pub state: ResolutionState,
+    pub proposer: Pubkey,
+    pub proposed_outcome: u8,
+    pub proposed_ts: i64,
+    /// Proposal can be disputed until this timestamp
+    pub dispute_deadline: i64,
+    pub challenger: Pubkey,
+    /// Bond posted by each party, fixed at proposal time
+    pub bond: u64,
+    pub final_outcome: u8,
+    pub bump: u8,
+    /// Settled price proposed for scalar markets
+    pub proposed_price: f64,
+    /// Settled price scalar markets were finalized to
+    pub final_price: f64,
+    pub reserved: [u8; 48],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, InitSpace)]
+pub enum ResolutionState {
+    Proposed,
+    /// Escalated to the config authority by a challenger
+    Disputed,
+    Finalized,
+}
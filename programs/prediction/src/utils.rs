@@ -1,6 +1,238 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    system_program::{allocate, assign, transfer, Allocate, Assign, Transfer},
+};
+use anchor_spl::token_interface::{
+    spl_token_2022::{
+        self,
+        extension::{
+            transfer_fee::TransferFeeConfig, BaseStateWithExtensions, ExtensionType,
+            StateWithExtensions,
+        },
+    },
+    Mint,
+};
 
-use crate::{imprecise_number, precise_number, BASE_SHARE_PRICE, ONE_IN_BASIS_POINTS};
+use spl_math::precise_number::PreciseNumber;
+
+use crate::{
+    error::PredictionError, imprecise_number, precise_number, Config, MAX_OUTCOMES, MAX_TITLE_LEN,
+    MAX_TRADING_MINT_DECIMALS, ONE_IN_BASIS_POINTS,
+};
+
+/// Collateral base units a single share is worth, i.e. one whole token of the trading mint.
+pub fn get_share_price(decimals: u8) -> Result<u64> {
+    require!(
+        decimals <= MAX_TRADING_MINT_DECIMALS,
+        PredictionError::InvalidMintDecimals
+    );
+
+    Ok(10_u64.pow(decimals.into()))
+}
+
+/// Rejects Token-2022 mints whose extensions would block or hide transfers in and out of markets,
+/// or let a permanent delegate move collateral out of market token accounts.
+pub fn validate_mint_extensions(mint: &AccountInfo) -> Result<()> {
+    if mint.owner != &spl_token_2022::ID {
+        return Ok(());
+    }
+
+    let mint_data = mint.try_borrow_data()?;
+    let mint_with_extensions =
+        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+
+    for extension_type in mint_with_extensions.get_extension_types()? {
+        require!(
+            !matches!(
+                extension_type,
+                ExtensionType::TransferHook
+                    | ExtensionType::PermanentDelegate
+                    | ExtensionType::NonTransferable
+                    | ExtensionType::ConfidentialTransferMint
+                    | ExtensionType::ConfidentialTransferFeeConfig
+            ),
+            PredictionError::UnsupportedMintExtension
+        );
+    }
+
+    Ok(())
+}
+
+/// Extra amount to send so that the recipient receives `amount` after the mint's transfer fee.
+pub fn get_transfer_inverse_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if mint.owner != &spl_token_2022::ID {
+        return Ok(0);
+    }
+
+    let mint_data = mint.try_borrow_data()?;
+    let mint_with_extensions =
+        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+
+    match mint_with_extensions.get_extension::<TransferFeeConfig>() {
+        Ok(transfer_fee_config) => Ok(transfer_fee_config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(PredictionError::MathOverflow)?),
+        Err(_) => Ok(0),
+    }
+}
+
+/// Validates market parameters against config and the trading mint.
+pub fn validate_market_args(
+    config: &Config,
+    trading_mint: &InterfaceAccount<Mint>,
+    resolve_ts: i64,
+    title: &str,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(resolve_ts > now, PredictionError::ResolveTsInPast);
+    require!(
+        resolve_ts - now >= config.min_market_duration,
+        PredictionError::MarketDurationTooShort
+    );
+    require!(
+        resolve_ts - now <= config.max_market_duration,
+        PredictionError::MarketDurationTooLong
+    );
+    require!(!title.is_empty(), PredictionError::EmptyTitle);
+    require!(title.len() <= MAX_TITLE_LEN, PredictionError::TitleTooLong);
+
+    validate_mint_extensions(&trading_mint.to_account_info())?;
+
+    require!(
+        config.share_price == get_share_price(trading_mint.decimals)?,
+        PredictionError::InvalidSharePrice
+    );
+
+    Ok(())
+}
+
+pub fn validate_target_price(target_price: f64) -> Result<()> {
+    require!(
+        target_price.is_finite() && target_price > 0.0,
+        PredictionError::InvalidTargetPrice
+    );
+
+    Ok(())
+}
+
+/// Deserializes an account still on its unversioned layout, failing if it was already migrated.
+pub fn read_legacy_account<T: AnchorDeserialize>(
+    account: &AccountInfo,
+    discriminator: &[u8],
+) -> Result<T> {
+    let data = account.try_borrow_data()?;
+
+    require!(
+        data.starts_with(discriminator),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+
+    // versioned layouts are strictly larger, so they either fail to parse or leave bytes behind
+    let mut legacy_data = &data[discriminator.len()..];
+    let legacy = T::deserialize(&mut legacy_data)
+        .map_err(|_| error!(PredictionError::AccountAlreadyMigrated))?;
+
+    require!(
+        legacy_data.is_empty(),
+        PredictionError::AccountAlreadyMigrated
+    );
+
+    Ok(legacy)
+}
+
+/// Deserializes an account on an earlier versioned layout, failing if it is already on `version`.
+/// Fields appended since that layout read as zero out of its reserved bytes.
+pub fn read_outdated_account<T: AccountDeserialize + Discriminator>(
+    account: &AccountInfo,
+    version: u8,
+) -> Result<T> {
+    let data = account.try_borrow_data()?;
+
+    // `version` is the first field of every versioned layout
+    require!(
+        data.get(T::DISCRIMINATOR.len())
+            .is_some_and(|&account_version| account_version < version),
+        PredictionError::AccountAlreadyMigrated
+    );
+
+    T::try_deserialize(&mut &data[..])
+}
+
+/// Transfers from `payer` whatever `account` lacks to be rent exempt at `space` bytes.
+fn top_up_rent<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let lamports = account.lamports();
+
+    if rent > lamports {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            rent - lamports,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Creates a program-owned PDA of `space` bytes the way `#[account(init)]` does, topping up rent
+/// instead of using `create_account`, which fails if the address was already sent lamports.
+pub fn create_pda_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    top_up_rent(account, payer, system_program, space)?;
+
+    allocate(
+        CpiContext::new(
+            system_program.clone(),
+            Allocate {
+                account_to_allocate: account.clone(),
+            },
+        )
+        .with_signer(&[signer_seeds]),
+        space as u64,
+    )?;
+    assign(
+        CpiContext::new(
+            system_program.clone(),
+            Assign {
+                account_to_assign: account.clone(),
+            },
+        )
+        .with_signer(&[signer_seeds]),
+        &crate::ID,
+    )
+}
+
+/// Resizes `account` to `space`, topping up rent from `payer`, and writes `value` into it.
+pub fn write_migrated_account<'info, T: AccountSerialize>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    value: &T,
+) -> Result<()> {
+    top_up_rent(account, payer, system_program, space)?;
+
+    account.realloc(space, false)?;
+
+    let mut data = account.try_borrow_mut_data()?;
+    value.try_serialize(&mut &mut data[..])
+}
 
 pub fn calculate_price(
     shares: u64,
@@ -8,48 +240,102 @@ pub fn calculate_price(
     fail_shares: u64,
     is_pass: bool,
     fee_bps: u16,
+    share_price: u64,
 ) -> Result<(u64, u64)> {
-    let mut total_price = precise_number!((BASE_SHARE_PRICE as u64)
+    calculate_outcome_price(
+        shares,
+        &[pass_shares, fail_shares],
+        if is_pass { 0 } else { 1 },
+        fee_bps,
+        share_price,
+    )
+}
+
+/// Fee and net price of `shares` in `outcome`. Once every outcome has been traded, the base
+/// price is scaled by the outcome's share of all outcome shares times the number of outcomes.
+pub fn calculate_outcome_price(
+    shares: u64,
+    outcome_shares: &[u64],
+    outcome: usize,
+    fee_bps: u16,
+    share_price: u64,
+) -> Result<(u64, u64)> {
+    let mut total_price = precise_number!(share_price
         .checked_mul(shares)
-        .unwrap()
+        .ok_or(PredictionError::MathOverflow)?
         .into());
 
-    if pass_shares != 0 && fail_shares != 0 {
-        let total_shares = pass_shares.checked_add(fail_shares).unwrap();
-        let pass_ratio = precise_number!(pass_shares.into())
+    if outcome_shares.iter().all(|&shares| shares != 0) {
+        let total_shares = outcome_shares
+            .iter()
+            .try_fold(0_u64, |total, &shares| total.checked_add(shares))
+            .ok_or(PredictionError::MathOverflow)?;
+        let ratio = precise_number!(outcome_shares[outcome].into())
             .checked_div(&precise_number!(total_shares.into()))
-            .unwrap();
-
-        let ratio = if is_pass {
-            pass_ratio
-        } else {
-            precise_number!(1).checked_sub(&pass_ratio).unwrap()
-        };
-
-        let one_half = precise_number!(1).checked_div(&precise_number!(2)).unwrap();
-
-        let imbalance = ratio.checked_sub(&one_half).unwrap();
-        let multiplier_adjustment = imbalance.checked_mul(&precise_number!(2)).unwrap();
-        let price_multiplier = precise_number!(1)
-            .checked_add(&multiplier_adjustment)
-            .unwrap();
-        total_price = total_price.checked_mul(&price_multiplier).unwrap();
+            .ok_or(PredictionError::DivisionByZero)?;
+
+        let price_multiplier = ratio
+            .checked_mul(&precise_number!(outcome_shares.len() as u128))
+            .ok_or(PredictionError::MathOverflow)?;
+        total_price = total_price
+            .checked_mul(&price_multiplier)
+            .ok_or(PredictionError::MathOverflow)?;
     }
 
+    apply_fee(total_price, fee_bps)
+}
+
+/// Fee and net price of a sale whose total is fixed at `total_price`.
+pub fn split_fee(total_price: u64, fee_bps: u16) -> Result<(u64, u64)> {
+    apply_fee(precise_number!(total_price.into()), fee_bps)
+}
+
+fn apply_fee(total_price: PreciseNumber, fee_bps: u16) -> Result<(u64, u64)> {
     let fee_multiplier = precise_number!(fee_bps.into())
         .checked_div(&precise_number!(ONE_IN_BASIS_POINTS.into()))
-        .unwrap();
+        .ok_or(PredictionError::DivisionByZero)?;
 
     let fee = total_price
         .checked_mul(&fee_multiplier)
-        .unwrap()
-        .floor()
-        .unwrap();
+        .and_then(|fee| fee.floor())
+        .ok_or(PredictionError::MathOverflow)?;
 
-    let price = total_price.checked_sub(&fee).unwrap().floor().unwrap();
+    let price = total_price
+        .checked_sub(&fee)
+        .and_then(|price| price.floor())
+        .ok_or(PredictionError::MathOverflow)?;
 
     Ok((
-        imprecise_number!(fee) as u64,
-        imprecise_number!(price) as u64,
+        to_u64(imprecise_number!(fee))?,
+        to_u64(imprecise_number!(price))?,
     ))
 }
+
+/// Narrows an imprecise result back to token units.
+pub fn to_u64(value: u128) -> Result<u64> {
+    u64::try_from(value).map_err(|_| error!(PredictionError::MathOverflow))
+}
+
+/// PnL realized by receiving `amount` for shares bought at `cost_basis`.
+pub fn get_realized_pnl(amount: u64, cost_basis: u64) -> Result<i64> {
+    let to_i64 = |value: u64| i64::try_from(value).map_err(|_| PredictionError::MathOverflow);
+
+    to_i64(amount)?
+        .checked_sub(to_i64(cost_basis)?)
+        .ok_or(error!(PredictionError::MathOverflow))
+}
+
+/// Validates categorical bucket bounds, returning the number of outcomes they delimit.
+pub fn validate_outcome_bounds(bounds: &[f64]) -> Result<u8> {
+    require!(
+        !bounds.is_empty() && bounds.len() < MAX_OUTCOMES,
+        PredictionError::InvalidOutcomeBounds
+    );
+    require!(
+        bounds.iter().all(|bound| bound.is_finite() && *bound > 0.0)
+            && bounds.windows(2).all(|pair| pair[0] < pair[1]),
+        PredictionError::InvalidOutcomeBounds
+    );
+
+    Ok(bounds.len() as u8 + 1)
+}
@@ -0,0 +1,877 @@
+//! LiteSVM harness shared by the integration tests: loads the program built by `anchor build`,
+//! the SOL/USD Pyth fixture shared with the TypeScript suite and a Token-2022 trading mint, and
+//! sends the program's instructions through their real entrypoints.
+
+#![allow(dead_code)]
+
+use std::{env, fs, path::PathBuf};
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program_pack::Pack, system_instruction},
+    system_program, AccountDeserialize, Discriminator, InstructionData, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::{
+        self, get_associated_token_address_with_program_id,
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+    },
+    token_2022::spl_token_2022::{
+        self,
+        extension::{transfer_fee::instruction::initialize_transfer_fee_config, ExtensionType},
+        instruction::{initialize_mint2, mint_to},
+    },
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use litesvm::LiteSVM;
+use prediction::{
+    accounts, instruction, CreateMarketArgs, CreateMarketTemplateArgs, CreateScalarMarketArgs,
+    FinalizeOutcomeArgs, InitializeConfigArgs, PriceCondition, ProposeOutcomeArgs, StrikeRule,
+    TradeQuote, TradeSharesArgs, CONFIG_SEED, MARKET_SEED, MARKET_TEMPLATE_SEED, POSITION_SEED,
+    RESOLUTION_SEED,
+};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use serde_json::Value;
+use solana_account::Account;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+use solana_transaction_error::TransactionError;
+
+pub const DECIMALS: u8 = 6;
+pub const FEE_BPS: u16 = 100;
+pub const MIN_MARKET_DURATION: i64 = 60;
+pub const MAX_MARKET_DURATION: i64 = 365 * 24 * 60 * 60;
+pub const PROPOSAL_BOND: u64 = 10_u64.pow(DECIMALS as u32);
+pub const DISPUTE_WINDOW: i64 = 2 * 60 * 60;
+/// Trading mint balance each trader starts with.
+pub const TRADER_BALANCE: u64 = 1_000 * 10_u64.pow(DECIMALS as u32);
+
+pub type TxResult = std::result::Result<(), TransactionError>;
+
+/// Program built by `anchor build`, or the directory in `SBF_OUT_DIR` as with
+/// `solana-program-test`.
+fn program_path() -> PathBuf {
+    env::var_os("SBF_OUT_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target/deploy"))
+        .join("prediction.so")
+}
+
+fn load_program(svm: &mut LiteSVM) {
+    let path = program_path();
+    let program = fs::read(&path)
+        .unwrap_or_else(|_| panic!("{} not found, run `anchor build` first", path.display()));
+
+    svm.add_program(prediction::ID, &program);
+}
+
+/// Account dumped from a cluster into `tests/fixtures`.
+pub struct Fixture {
+    pub key: Pubkey,
+    pub account: Account,
+}
+
+impl Fixture {
+    pub fn load(name: &str) -> Self {
+        let path = format!("{}/../../tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR"));
+        let json: Value = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        let account = &json["account"];
+
+        Fixture {
+            key: json["pubkey"].as_str().unwrap().parse().unwrap(),
+            account: Account {
+                lamports: account["lamports"].as_u64().unwrap(),
+                data: STANDARD
+                    .decode(account["data"][0].as_str().unwrap())
+                    .unwrap(),
+                owner: account["owner"].as_str().unwrap().parse().unwrap(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        }
+    }
+
+    pub fn price_update(&self) -> PriceUpdateV2 {
+        PriceUpdateV2::try_deserialize(&mut &self.account.data[..]).unwrap()
+    }
+}
+
+pub struct TestContext {
+    pub svm: LiteSVM,
+    /// Config authority, also paying for markets
+    pub authority: Keypair,
+    pub mint_authority: Keypair,
+    pub mint: Pubkey,
+    pub oracle: Fixture,
+    /// Logs of the last transaction that succeeded
+    pub logs: Vec<String>,
+}
+
+impl TestContext {
+    /// Loads the program, the Pyth fixture and a Token-2022 trading mint, with the clock set to
+    /// the fixture's publish time. Panics when the program has not been built.
+    pub fn new() -> Self {
+        let mut svm = LiteSVM::new();
+
+        load_program(&mut svm);
+
+        let oracle = Fixture::load("sol_usd_price_update_v2.json");
+        svm.set_account(oracle.key, oracle.account.clone()).unwrap();
+
+        let authority = Keypair::new();
+        svm.airdrop(&authority.pubkey(), 100_000_000_000).unwrap();
+
+        let mut ctx = TestContext {
+            svm,
+            authority,
+            mint_authority: Keypair::new(),
+            mint: Pubkey::default(),
+            oracle,
+            logs: Vec::new(),
+        };
+
+        ctx.set_time(ctx.publish_time());
+        ctx.mint = ctx.create_mint(DECIMALS);
+
+        ctx
+    }
+
+    pub fn publish_time(&self) -> i64 {
+        self.oracle.price_update().price_message.publish_time
+    }
+
+    /// Price of the fixture as a float.
+    pub fn fixture_price(&self) -> f64 {
+        let message = self.oracle.price_update().price_message;
+
+        message.price as f64 * 10_f64.powi(message.exponent)
+    }
+
+    /// Rewrites the fixture's price as `price * 10^exponent`, keeping its feed and publish time.
+    pub fn set_fixture_price(&mut self, price: i64, exponent: i32) {
+        let mut update = self.oracle.price_update();
+        update.price_message.price = price;
+        update.price_message.exponent = exponent;
+
+        let mut data = Vec::new();
+        update.try_serialize(&mut data).unwrap();
+        self.oracle.account.data[..data.len()].copy_from_slice(&data);
+
+        self.svm
+            .set_account(self.oracle.key, self.oracle.account.clone())
+            .unwrap();
+    }
+
+    pub fn now(&self) -> i64 {
+        self.svm.get_sysvar::<Clock>().unix_timestamp
+    }
+
+    pub fn set_time(&mut self, unix_timestamp: i64) {
+        let clock = Clock {
+            unix_timestamp,
+            ..self.svm.get_sysvar::<Clock>()
+        };
+
+        self.svm.set_sysvar(&clock);
+    }
+
+    /// Signs `instructions` with `payer` and `signers`, expiring the blockhash afterwards so the
+    /// same instructions can be sent again. Program logs are printed when the transaction fails.
+    pub fn send(
+        &mut self,
+        instructions: &[Instruction],
+        payer: &Keypair,
+        signers: &[&Keypair],
+    ) -> TxResult {
+        let mut all_signers = vec![payer];
+        all_signers.extend_from_slice(signers);
+
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            self.svm.latest_blockhash(),
+        );
+        let result = self.svm.send_transaction(transaction);
+
+        self.svm.expire_blockhash();
+        match result {
+            Ok(meta) => {
+                self.logs = meta.logs;
+                Ok(())
+            }
+            Err(failed) => {
+                eprintln!("{}", failed.meta.logs.join("\n"));
+                Err(failed.err)
+            }
+        }
+    }
+
+    /// Events of type `T` emitted by the last transaction that succeeded.
+    pub fn events<T: AnchorDeserialize + Discriminator>(&self) -> Vec<T> {
+        self.logs
+            .iter()
+            .filter_map(|log| log.strip_prefix("Program data: "))
+            .filter_map(|data| STANDARD.decode(data).ok())
+            .filter_map(|data| {
+                data.strip_prefix(T::DISCRIMINATOR)
+                    .map(|mut event| T::deserialize(&mut event).unwrap())
+            })
+            .collect()
+    }
+
+    pub fn account<T: AccountDeserialize>(&self, key: &Pubkey) -> T {
+        let account = self.svm.get_account(key).unwrap();
+
+        T::try_deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    pub fn token_balance(&self, token_account: &Pubkey) -> u64 {
+        let account = self.svm.get_account(token_account).unwrap();
+
+        spl_token_2022::state::Account::unpack_from_slice(
+            &account.data[..spl_token_2022::state::Account::LEN],
+        )
+        .unwrap()
+        .amount
+    }
+
+    pub fn create_mint(&mut self, decimals: u8) -> Pubkey {
+        self.create_mint_with_transfer_fee(decimals, None)
+    }
+
+    /// Creates a Token-2022 mint, charging `transfer_fee` as basis points and maximum fee if set.
+    pub fn create_mint_with_transfer_fee(
+        &mut self,
+        decimals: u8,
+        transfer_fee: Option<(u16, u64)>,
+    ) -> Pubkey {
+        let mint = Keypair::new();
+        let extensions: &[ExtensionType] = match transfer_fee {
+            Some(_) => &[ExtensionType::TransferFeeConfig],
+            None => &[],
+        };
+        let space =
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(extensions)
+                .unwrap();
+        let payer = self.authority.insecure_clone();
+
+        let mut instructions = vec![system_instruction::create_account(
+            &payer.pubkey(),
+            &mint.pubkey(),
+            self.svm.minimum_balance_for_rent_exemption(space),
+            space as u64,
+            &spl_token_2022::ID,
+        )];
+
+        if let Some((basis_points, maximum_fee)) = transfer_fee {
+            instructions.push(
+                initialize_transfer_fee_config(
+                    &spl_token_2022::ID,
+                    &mint.pubkey(),
+                    None,
+                    None,
+                    basis_points,
+                    maximum_fee,
+                )
+                .unwrap(),
+            );
+        }
+
+        instructions.push(
+            initialize_mint2(
+                &spl_token_2022::ID,
+                &mint.pubkey(),
+                &self.mint_authority.pubkey(),
+                None,
+                decimals,
+            )
+            .unwrap(),
+        );
+
+        self.send(&instructions, &payer, &[&mint]).unwrap();
+
+        mint.pubkey()
+    }
+
+    /// Funds a new wallet with SOL and `TRADER_BALANCE` of the trading mint.
+    pub fn trader(&mut self) -> Keypair {
+        let trader = Keypair::new();
+
+        self.svm.airdrop(&trader.pubkey(), 10_000_000_000).unwrap();
+        self.fund_token_account(&trader.pubkey(), TRADER_BALANCE);
+
+        trader
+    }
+
+    /// Creates the trading mint token account of `owner` if needed and mints `amount` into it.
+    pub fn fund_token_account(&mut self, owner: &Pubkey, amount: u64) {
+        let token_account = self.token_account(owner);
+        let payer = self.authority.insecure_clone();
+        let mint_authority = self.mint_authority.insecure_clone();
+
+        self.send(
+            &[
+                create_associated_token_account_idempotent(
+                    &payer.pubkey(),
+                    owner,
+                    &self.mint,
+                    &spl_token_2022::ID,
+                ),
+                mint_to(
+                    &spl_token_2022::ID,
+                    &self.mint,
+                    &token_account,
+                    &mint_authority.pubkey(),
+                    &[],
+                    amount,
+                )
+                .unwrap(),
+            ],
+            &payer,
+            &[&mint_authority],
+        )
+        .unwrap();
+    }
+
+    /// Writes a rent-exempt account owned by the program, as left behind by earlier versions.
+    pub fn set_program_account(&mut self, key: &Pubkey, data: Vec<u8>) {
+        self.svm
+            .set_account(
+                *key,
+                Account {
+                    lamports: self.svm.minimum_balance_for_rent_exemption(data.len()),
+                    data,
+                    owner: prediction::ID,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            )
+            .unwrap();
+    }
+
+    pub fn token_account(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(owner, &self.mint, &spl_token_2022::ID)
+    }
+
+    pub fn config_pda() -> Pubkey {
+        Pubkey::find_program_address(&[CONFIG_SEED], &prediction::ID).0
+    }
+
+    pub fn market_pda(id: u64) -> Pubkey {
+        Pubkey::find_program_address(&[MARKET_SEED, id.to_le_bytes().as_ref()], &prediction::ID).0
+    }
+
+    pub fn market_template_pda(id: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[MARKET_TEMPLATE_SEED, id.to_le_bytes().as_ref()],
+            &prediction::ID,
+        )
+        .0
+    }
+
+    pub fn position_pda(authority: &Pubkey, market: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[POSITION_SEED, authority.as_ref(), market.as_ref()],
+            &prediction::ID,
+        )
+        .0
+    }
+
+    pub fn resolution_pda(market: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[RESOLUTION_SEED, market.as_ref()], &prediction::ID).0
+    }
+
+    fn instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+        Instruction {
+            program_id: prediction::ID,
+            accounts: accounts.to_account_metas(None),
+            data: data.data(),
+        }
+    }
+
+    pub fn initialize_config(&mut self) -> TxResult {
+        let config = Self::config_pda();
+        let authority = self.authority.insecure_clone();
+
+        self.send(
+            &[Self::instruction(
+                accounts::InitializeConfig {
+                    authority: authority.pubkey(),
+                    config,
+                    mint: self.mint,
+                    config_authority_token_account: self.token_account(&config),
+                    system_program: system_program::ID,
+                    token_program: spl_token_2022::ID,
+                    associated_token_program: associated_token::ID,
+                },
+                instruction::InitializeConfig {
+                    args: InitializeConfigArgs {
+                        fee_bps: FEE_BPS,
+                        min_market_duration: MIN_MARKET_DURATION,
+                        max_market_duration: MAX_MARKET_DURATION,
+                        proposal_bond: PROPOSAL_BOND,
+                        dispute_window: DISPUTE_WINDOW,
+                    },
+                },
+            )],
+            &authority,
+            &[],
+        )
+    }
+
+    pub fn migrate_config(&mut self) -> TxResult {
+        let authority = self.authority.insecure_clone();
+
+        self.send(
+            &[Self::instruction(
+                accounts::MigrateConfig {
+                    authority: authority.pubkey(),
+                    config: Self::config_pda(),
+                    mint: self.mint,
+                    system_program: system_program::ID,
+                },
+                instruction::MigrateConfig {},
+            )],
+            &authority,
+            &[],
+        )
+    }
+
+    pub fn migrate_market(
+        &mut self,
+        market: &Pubkey,
+        market_token_account: Option<Pubkey>,
+    ) -> TxResult {
+        let authority = self.authority.insecure_clone();
+
+        self.send(
+            &[Self::instruction(
+                accounts::MigrateMarket {
+                    payer: authority.pubkey(),
+                    config: Self::config_pda(),
+                    market: *market,
+                    trading_mint: self.mint,
+                    market_token_account,
+                    system_program: system_program::ID,
+                    token_program: spl_token_2022::ID,
+                },
+                instruction::MigrateMarket {},
+            )],
+            &authority,
+            &[],
+        )
+    }
+
+    pub fn migrate_position(&mut self, position: &Pubkey) -> TxResult {
+        let authority = self.authority.insecure_clone();
+
+        self.send(
+            &[Self::instruction(
+                accounts::MigratePosition {
+                    payer: authority.pubkey(),
+                    position: *position,
+                    system_program: system_program::ID,
+                },
+                instruction::MigratePosition {},
+            )],
+            &authority,
+            &[],
+        )
+    }
+
+    /// Creates the next market against the fixture, resolving at `resolve_ts`.
+    pub fn create_market(
+        &mut self,
+        resolve_ts: i64,
+        target_price: f64,
+    ) -> std::result::Result<Pubkey, TransactionError> {
+        let config = Self::config_pda();
+        let market_count = self.account::<prediction::Config>(&config).market_count;
+        let market = Self::market_pda(market_count);
+        let authority = self.authority.insecure_clone();
+
+        self.send(
+            &[Self::instruction(
+                accounts::CreateMarket {
+                    authority: authority.pubkey(),
+                    config,
+                    market,
+                    price_update_v2: self.oracle.key,
+                    trading_mint: self.mint,
+                    market_token_account: self.token_account(&market),
+                    system_program: system_program::ID,
+                    token_program: spl_token_2022::ID,
+                    associated_token_program: associated_token::ID,
+                },
+                instruction::CreateMarket {
+                    args: CreateMarketArgs {
+                        resolve_ts,
+                        target_price,
+                        title: String::from("SOL above target"),
+                    },
+                },
+            )],
+            &authority,
+            &[],
+        )?;
+
+        Ok(market)
+    }
+
+    pub fn create_scalar_market(
+        &mut self,
+        resolve_ts: i64,
+        lower: f64,
+        upper: f64,
+    ) -> std::result::Result<Pubkey, TransactionError> {
+        let config = Self::config_pda();
+        let market_count = self.account::<prediction::Config>(&config).market_count;
+        let market = Self::market_pda(market_count);
+        let authority = self.authority.insecure_clone();
+
+        self.send(
+            &[Self::instruction(
+                accounts::CreateScalarMarket {
+                    authority: authority.pubkey(),
+                    config,
+                    market,
+                    price_update_v2: self.oracle.key,
+                    trading_mint: self.mint,
+                    market_token_account: self.token_account(&market),
+                    system_program: system_program::ID,
+                    token_program: spl_token_2022::ID,
+                    associated_token_program: associated_token::ID,
+                },
+                instruction::CreateScalarMarket {
+                    args: CreateScalarMarketArgs {
+                        resolve_ts,
+                        lower,
+                        upper,
+                        title: String::from("SOL price"),
+                    },
+                },
+            )],
+            &authority,
+            &[],
+        )?;
+
+        Ok(market)
+    }
+
+    /// Creates the next template against the fixture, spawning markets every `duration`.
+    pub fn create_market_template(
+        &mut self,
+        duration: i64,
+        target_price: f64,
+    ) -> std::result::Result<Pubkey, TransactionError> {
+        let config = Self::config_pda();
+        let template_count = self.account::<prediction::Config>(&config).template_count;
+        let market_template = Self::market_template_pda(template_count);
+        let authority = self.authority.insecure_clone();
+
+        self.send(
+            &[Self::instruction(
+                accounts::CreateMarketTemplate {
+                    authority: authority.pubkey(),
+                    config,
+                    market_template,
+                    price_update_v2: self.oracle.key,
+                    system_program: system_program::ID,
+                },
+                instruction::CreateMarketTemplate {
+                    args: CreateMarketTemplateArgs {
+                        condition: PriceCondition::Above,
+                        duration,
+                        fee_bps: None,
+                        strike_rule: StrikeRule::Fixed,
+                        target_price,
+                        offset_bps: 0,
+                        title: String::from("SOL above target"),
+                    },
+                },
+            )],
+            &authority,
+            &[],
+        )?;
+
+        Ok(market_template)
+    }
+
+    /// Spawns the next market from `market_template`, rolling over from `previous_market`.
+    pub fn spawn_from_template(
+        &mut self,
+        market_template: &Pubkey,
+        previous_market: Option<Pubkey>,
+    ) -> std::result::Result<Pubkey, TransactionError> {
+        let config = Self::config_pda();
+        let market_count = self.account::<prediction::Config>(&config).market_count;
+        let market = Self::market_pda(market_count);
+        let payer = self.authority.insecure_clone();
+
+        self.send(
+            &[Self::instruction(
+                accounts::SpawnFromTemplate {
+                    payer: payer.pubkey(),
+                    config,
+                    market_template: *market_template,
+                    previous_market,
+                    market,
+                    price_update_v2: self.oracle.key,
+                    trading_mint: self.mint,
+                    market_token_account: self.token_account(&market),
+                    system_program: system_program::ID,
+                    token_program: spl_token_2022::ID,
+                    associated_token_program: associated_token::ID,
+                },
+                instruction::SpawnFromTemplate {},
+            )],
+            &payer,
+            &[],
+        )?;
+
+        Ok(market)
+    }
+
+    pub fn open_position(&mut self, trader: &Keypair, market: &Pubkey) -> TxResult {
+        self.send(
+            &[Self::instruction(
+                accounts::OpenPosition {
+                    authority: trader.pubkey(),
+                    market: *market,
+                    position: Self::position_pda(&trader.pubkey(), market),
+                    system_program: system_program::ID,
+                },
+                instruction::OpenPosition {},
+            )],
+            trader,
+            &[],
+        )
+    }
+
+    pub fn trade(
+        &mut self,
+        trader: &Keypair,
+        market: &Pubkey,
+        shares: u64,
+        is_pass: bool,
+        is_buy: bool,
+    ) -> TxResult {
+        let config = Self::config_pda();
+
+        self.send(
+            &[Self::instruction(
+                accounts::TradeShares {
+                    authority: trader.pubkey(),
+                    config_authority: self.authority.pubkey(),
+                    config,
+                    market: *market,
+                    position: Self::position_pda(&trader.pubkey(), market),
+                    trading_mint: self.mint,
+                    authority_token_account: self.token_account(&trader.pubkey()),
+                    market_token_account: self.token_account(market),
+                    config_authority_token_account: self.token_account(&config),
+                    system_program: system_program::ID,
+                    token_program: spl_token_2022::ID,
+                    associated_token_program: associated_token::ID,
+                },
+                instruction::TradeShares {
+                    args: TradeSharesArgs {
+                        shares,
+                        is_pass,
+                        is_buy,
+                    },
+                },
+            )],
+            trader,
+            &[],
+        )
+    }
+
+    /// Settles `market` against the fixture.
+    pub fn settle(&mut self, market: &Pubkey) -> TxResult {
+        let price_update_v2 = self.oracle.key;
+
+        self.settle_against(market, &price_update_v2)
+    }
+
+    /// Settles `market` against `price_update_v2`.
+    pub fn settle_against(&mut self, market: &Pubkey, price_update_v2: &Pubkey) -> TxResult {
+        let authority = self.authority.insecure_clone();
+
+        self.send(
+            &[Self::instruction(
+                accounts::SettleMarket {
+                    authority: authority.pubkey(),
+                    config: Self::config_pda(),
+                    market: *market,
+                    price_update_v2: *price_update_v2,
+                    price_samples: None,
+                },
+                instruction::SettleMarket {},
+            )],
+            &authority,
+            &[],
+        )
+    }
+
+    pub fn propose(
+        &mut self,
+        proposer: &Keypair,
+        market: &Pubkey,
+        outcome: u8,
+        settled_price: Option<f64>,
+    ) -> TxResult {
+        let resolution = Self::resolution_pda(market);
+
+        self.send(
+            &[Self::instruction(
+                accounts::ProposeOutcome {
+                    proposer: proposer.pubkey(),
+                    config: Self::config_pda(),
+                    market: *market,
+                    resolution,
+                    trading_mint: self.mint,
+                    proposer_token_account: self.token_account(&proposer.pubkey()),
+                    resolution_token_account: self.token_account(&resolution),
+                    system_program: system_program::ID,
+                    token_program: spl_token_2022::ID,
+                    associated_token_program: associated_token::ID,
+                },
+                instruction::ProposeOutcome {
+                    args: ProposeOutcomeArgs {
+                        outcome,
+                        settled_price,
+                    },
+                },
+            )],
+            proposer,
+            &[],
+        )
+    }
+
+    pub fn dispute(&mut self, challenger: &Keypair, market: &Pubkey) -> TxResult {
+        let resolution = Self::resolution_pda(market);
+
+        self.send(
+            &[Self::instruction(
+                accounts::DisputeOutcome {
+                    challenger: challenger.pubkey(),
+                    config: Self::config_pda(),
+                    resolution,
+                    trading_mint: self.mint,
+                    challenger_token_account: self.token_account(&challenger.pubkey()),
+                    resolution_token_account: self.token_account(&resolution),
+                    token_program: spl_token_2022::ID,
+                    associated_token_program: associated_token::ID,
+                },
+                instruction::DisputeOutcome {},
+            )],
+            challenger,
+            &[],
+        )
+    }
+
+    /// Finalizes the resolution of `market` as the config authority, paying the bonds to
+    /// `winner`.
+    pub fn finalize(
+        &mut self,
+        market: &Pubkey,
+        winner: &Pubkey,
+        outcome: Option<u8>,
+        settled_price: Option<f64>,
+    ) -> TxResult {
+        let authority = self.authority.insecure_clone();
+        let resolution = Self::resolution_pda(market);
+
+        self.send(
+            &[Self::instruction(
+                accounts::FinalizeOutcome {
+                    authority: authority.pubkey(),
+                    config: Self::config_pda(),
+                    market: *market,
+                    resolution,
+                    trading_mint: self.mint,
+                    winner_token_account: self.token_account(winner),
+                    resolution_token_account: self.token_account(&resolution),
+                    token_program: spl_token_2022::ID,
+                },
+                instruction::FinalizeOutcome {
+                    args: FinalizeOutcomeArgs {
+                        outcome,
+                        settled_price,
+                    },
+                },
+            )],
+            &authority,
+            &[],
+        )
+    }
+
+    /// Quotes a trade through `quote_trade` without sending it.
+    pub fn quote(
+        &mut self,
+        market: &Pubkey,
+        shares: u64,
+        is_pass: bool,
+        is_buy: bool,
+    ) -> TradeQuote {
+        let transaction = Transaction::new_signed_with_payer(
+            &[Self::instruction(
+                accounts::QuoteTrade {
+                    config: Self::config_pda(),
+                    market: *market,
+                    trading_mint: self.mint,
+                },
+                instruction::QuoteTrade {
+                    args: TradeSharesArgs {
+                        shares,
+                        is_pass,
+                        is_buy,
+                    },
+                },
+            )],
+            Some(&self.authority.pubkey()),
+            &[&self.authority],
+            self.svm.latest_blockhash(),
+        );
+        let simulated = self.svm.simulate_transaction(transaction).unwrap();
+
+        TradeQuote::deserialize(&mut &simulated.meta.return_data.data[..]).unwrap()
+    }
+
+    pub fn claim(&mut self, trader: &Keypair, market: &Pubkey) -> TxResult {
+        self.send(
+            &[Self::instruction(
+                accounts::ClaimWinnings {
+                    authority: trader.pubkey(),
+                    market: *market,
+                    position: Self::position_pda(&trader.pubkey(), market),
+                    trading_mint: self.mint,
+                    authority_token_account: self.token_account(&trader.pubkey()),
+                    market_token_account: self.token_account(market),
+                    system_program: system_program::ID,
+                    token_program: spl_token_2022::ID,
+                    associated_token_program: associated_token::ID,
+                },
+                instruction::ClaimWinnings {},
+            )],
+            trader,
+            &[],
+        )
+    }
+}
+
+/// Asserts a transaction failed with `error` from the program.
+pub fn assert_program_error(result: TxResult, error: impl Into<u32>) {
+    let err = result.expect_err("transaction should fail");
+
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            anchor_lang::solana_program::instruction::InstructionError::Custom(error.into())
+        )
+    );
+}
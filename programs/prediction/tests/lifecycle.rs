@@ -0,0 +1,218 @@
+//! Lifecycle tests that send the program's instructions through LiteSVM against the SOL/USD Pyth
+//! fixture shared with the TypeScript suite, covering a market from creation to claims without
+//! Node. They need the program built, so run `anchor build` and then `cargo test -- --ignored`.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{assert_program_error, TestContext, DECIMALS, TRADER_BALANCE};
+use prediction::{
+    error::PredictionError, oracle::MAX_PRICE_AGE, Market, MarketState, OracleSource, Position,
+    WinningsClaimed,
+};
+use solana_signer::Signer;
+
+/// Seconds between market creation and `resolve_ts`, which is the fixture's publish time.
+const MARKET_DURATION: i64 = 60 * 60;
+
+/// Initializes the config and creates a market resolving at the fixture's publish time, with the
+/// clock left at creation so it can be traded.
+fn setup(target_offset: f64) -> (TestContext, Pubkey) {
+    let mut ctx = TestContext::new();
+    let publish_time = ctx.publish_time();
+    let target_price = ctx.fixture_price() + target_offset;
+
+    ctx.set_time(publish_time - MARKET_DURATION);
+    ctx.initialize_config().unwrap();
+
+    let market = ctx.create_market(publish_time, target_price).unwrap();
+
+    (ctx, market)
+}
+
+#[test]
+#[ignore = "requires the program built by `anchor build`"]
+fn market_is_created_against_the_pyth_fixture() {
+    let (ctx, market) = setup(0.0);
+    let market = ctx.account::<Market>(&market);
+
+    assert_eq!(ctx.oracle.account.owner, pyth_solana_receiver_sdk::ID);
+    assert_eq!(market.price_update_v2, ctx.oracle.key);
+    assert!(market.oracle_source == OracleSource::Pyth);
+    assert!(market.state == MarketState::Initialized);
+}
+
+#[test]
+#[ignore = "requires the program built by `anchor build`"]
+fn market_lifecycle_pays_winners_out_of_collateral() {
+    let (mut ctx, market) = setup(-1.0);
+    let alice = ctx.trader();
+    let bob = ctx.trader();
+
+    for trader in [&alice, &bob] {
+        ctx.open_position(trader, &market).unwrap();
+    }
+
+    ctx.trade(&alice, &market, 30, true, true).unwrap();
+    ctx.trade(&bob, &market, 20, false, true).unwrap();
+    ctx.trade(&alice, &market, 10, true, false).unwrap();
+    ctx.trade(&bob, &market, 15, true, true).unwrap();
+
+    let state = ctx.account::<Market>(&market);
+    let alice_position =
+        ctx.account::<Position>(&TestContext::position_pda(&alice.pubkey(), &market));
+
+    assert_eq!(state.pass_shares, 35);
+    assert_eq!(state.fail_shares, 20);
+    assert_eq!(alice_position.pass_shares, 20);
+    assert_eq!(
+        ctx.token_balance(&ctx.token_account(&market)),
+        state.total_collateral,
+        "the market token account holds exactly the collateral"
+    );
+
+    ctx.set_time(ctx.publish_time());
+    ctx.settle(&market).unwrap();
+
+    let state = ctx.account::<Market>(&market);
+    assert!(state.state == MarketState::Passed);
+
+    let alice_before = ctx.token_balance(&ctx.token_account(&alice.pubkey()));
+    let bob_before = ctx.token_balance(&ctx.token_account(&bob.pubkey()));
+
+    let alice_position =
+        ctx.account::<Position>(&TestContext::position_pda(&alice.pubkey(), &market));
+
+    ctx.claim(&alice, &market).unwrap();
+
+    let mut events = ctx.events::<WinningsClaimed>();
+    assert_eq!(events.len(), 1);
+    let claimed = events.remove(0);
+
+    ctx.claim(&bob, &market).unwrap();
+
+    let alice_winnings = ctx.token_balance(&ctx.token_account(&alice.pubkey())) - alice_before;
+
+    // the claim realizes the cost basis of the shares still held into the PnL reported
+    assert_eq!(claimed.authority, alice.pubkey());
+    assert_eq!(claimed.amount, alice_winnings);
+    assert_eq!(
+        claimed.realized_pnl,
+        alice_position.realized_pnl + alice_winnings as i64 - alice_position.pass_cost_basis as i64
+    );
+    assert_eq!(claimed.total_fees_paid, alice_position.total_fees_paid);
+    let bob_winnings = ctx.token_balance(&ctx.token_account(&bob.pubkey())) - bob_before;
+
+    assert!(alice_winnings > 0 && bob_winnings > 0);
+    assert!(alice_winnings + bob_winnings <= state.total_collateral);
+    assert_eq!(
+        alice_winnings * 15 / 20,
+        bob_winnings,
+        "winnings split pro rata by PASS shares"
+    );
+    assert!(
+        ctx.svm
+            .get_account(&TestContext::position_pda(&alice.pubkey(), &market))
+            .is_none_or(|account| account.lamports == 0),
+        "claiming closes the position"
+    );
+}
+
+#[test]
+#[ignore = "requires the program built by `anchor build`"]
+fn market_below_target_fails_and_pays_fail_holders() {
+    let (mut ctx, market) = setup(1.0);
+    let alice = ctx.trader();
+    let bob = ctx.trader();
+
+    for trader in [&alice, &bob] {
+        ctx.open_position(trader, &market).unwrap();
+    }
+
+    ctx.trade(&alice, &market, 25, true, true).unwrap();
+    ctx.trade(&bob, &market, 25, false, true).unwrap();
+
+    ctx.set_time(ctx.publish_time());
+    ctx.settle(&market).unwrap();
+
+    let state = ctx.account::<Market>(&market);
+    assert!(state.state == MarketState::Failed);
+
+    assert_program_error(
+        ctx.claim(&alice, &market),
+        PredictionError::NoClaimableWinnings,
+    );
+
+    let bob_before = ctx.token_balance(&ctx.token_account(&bob.pubkey()));
+    ctx.claim(&bob, &market).unwrap();
+
+    assert_eq!(
+        ctx.token_balance(&ctx.token_account(&bob.pubkey())) - bob_before,
+        state.total_collateral
+    );
+    assert!(ctx.token_balance(&ctx.token_account(&bob.pubkey())) > TRADER_BALANCE);
+}
+
+#[test]
+#[ignore = "requires the program built by `anchor build`"]
+fn settlement_rejects_stale_fixture_price() {
+    let (mut ctx, market) = setup(0.0);
+
+    ctx.set_time(ctx.publish_time() + MAX_PRICE_AGE as i64 + 1);
+
+    assert_program_error(ctx.settle(&market), PredictionError::StalePrice);
+    assert!(ctx.account::<Market>(&market).state == MarketState::Initialized);
+}
+
+#[test]
+#[ignore = "requires the program built by `anchor build`"]
+fn selling_more_than_held_is_rejected() {
+    let (mut ctx, market) = setup(0.0);
+    let alice = ctx.trader();
+
+    ctx.open_position(&alice, &market).unwrap();
+    ctx.trade(&alice, &market, 5, true, true).unwrap();
+
+    assert_program_error(
+        ctx.trade(&alice, &market, 6, true, false),
+        PredictionError::InsufficientSharesToSell,
+    );
+}
+
+#[test]
+#[ignore = "requires the program built by `anchor build`"]
+fn buy_quote_covers_mint_transfer_fee() {
+    let mut ctx = TestContext::new();
+    let publish_time = ctx.publish_time();
+
+    // 1% transfer fee, uncapped
+    ctx.mint = ctx.create_mint_with_transfer_fee(DECIMALS, Some((100, u64::MAX)));
+    ctx.set_time(publish_time - MARKET_DURATION);
+    ctx.initialize_config().unwrap();
+
+    let market = ctx
+        .create_market(publish_time, ctx.fixture_price())
+        .unwrap();
+    let alice = ctx.trader();
+
+    ctx.open_position(&alice, &market).unwrap();
+
+    let quote = ctx.quote(&market, 10, true, true);
+    let balance = ctx.token_balance(&ctx.token_account(&alice.pubkey()));
+
+    ctx.trade(&alice, &market, 10, true, true).unwrap();
+
+    let state = ctx.account::<Market>(&market);
+
+    assert_eq!(
+        balance - ctx.token_balance(&ctx.token_account(&alice.pubkey())),
+        quote.fee + quote.amount,
+        "the quote is the exact cost of the buy"
+    );
+    assert!(quote.amount > state.total_collateral);
+    assert_eq!(
+        ctx.token_balance(&ctx.token_account(&market)),
+        state.total_collateral,
+        "the market receives the full price net of the transfer fee"
+    );
+}
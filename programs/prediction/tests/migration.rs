@@ -0,0 +1,264 @@
+//! Migration tests that write accounts on earlier layouts into LiteSVM and migrate them through
+//! the program. Run `anchor build` and then `cargo test -- --ignored`.
+
+mod common;
+
+use anchor_lang::{prelude::*, Discriminator};
+use common::{assert_program_error, TestContext, DECIMALS, FEE_BPS};
+use prediction::{
+    error::PredictionError, Config, Market, MarketSeeds, MarketState, MarketV0, Position,
+    PositionV0, CONFIG_SEED, CONFIG_VERSION, DEFAULT_DISPUTE_WINDOW, DEFAULT_MAX_MARKET_DURATION,
+    DEFAULT_MIN_MARKET_DURATION, MARKET_SEED, MARKET_VERSION, POSITION_SEED,
+};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+
+/// Collateral held by legacy markets, split 30/10 between PASS and FAIL shares.
+const LEGACY_COLLATERAL: u64 = 12_000_000;
+
+fn legacy_account_data<T: AnchorSerialize>(discriminator: &[u8], value: &T) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    value.serialize(&mut data).unwrap();
+    data
+}
+
+/// Writes a config on the v1 layout: version, authority, fee_bps, mint, share_price, bump and
+/// 128 reserved bytes.
+fn set_v1_config(ctx: &mut TestContext) {
+    let (config, bump) = Pubkey::find_program_address(&[CONFIG_SEED], &prediction::ID);
+
+    let mut data = Config::DISCRIMINATOR.to_vec();
+    data.push(1);
+    data.extend_from_slice(ctx.authority.pubkey().as_ref());
+    data.extend_from_slice(&FEE_BPS.to_le_bytes());
+    data.extend_from_slice(ctx.mint.as_ref());
+    data.extend_from_slice(&10_u64.pow(DECIMALS.into()).to_le_bytes());
+    data.push(bump);
+    data.extend_from_slice(&[0; 128]);
+
+    ctx.set_program_account(&config, data);
+}
+
+/// Legacy market seeded by the fixture and its publish time, holding `LEGACY_COLLATERAL`, along
+/// with the unversioned positions of a trader holding 20 PASS and one holding 10 PASS and 10 FAIL.
+struct LegacyMarket {
+    key: Pubkey,
+    bump: u8,
+    pass_holder: Keypair,
+    mixed_holder: Keypair,
+}
+
+impl LegacyMarket {
+    fn new(ctx: &mut TestContext) -> Self {
+        let publish_time = ctx.publish_time();
+
+        ctx.set_time(publish_time - 60 * 60);
+        ctx.initialize_config().unwrap();
+
+        let (key, bump) = Pubkey::find_program_address(
+            &[
+                MARKET_SEED,
+                ctx.oracle.key.as_ref(),
+                publish_time.to_le_bytes().as_ref(),
+            ],
+            &prediction::ID,
+        );
+
+        ctx.fund_token_account(&key, LEGACY_COLLATERAL);
+
+        let legacy_market = LegacyMarket {
+            key,
+            bump,
+            pass_holder: ctx.trader(),
+            mixed_holder: ctx.trader(),
+        };
+
+        for (trader, pass_shares, fail_shares) in [
+            (&legacy_market.pass_holder, 20, 0),
+            (&legacy_market.mixed_holder, 10, 10),
+        ] {
+            let (position, bump) = Pubkey::find_program_address(
+                &[POSITION_SEED, trader.pubkey().as_ref(), key.as_ref()],
+                &prediction::ID,
+            );
+
+            ctx.set_program_account(
+                &position,
+                legacy_account_data(
+                    Position::DISCRIMINATOR,
+                    &PositionV0 {
+                        authority: trader.pubkey(),
+                        market: key,
+                        pass_shares,
+                        fail_shares,
+                        bump,
+                    },
+                ),
+            );
+        }
+
+        legacy_market
+    }
+
+    /// Migrates the market and positions, settles the market as passed and checks PASS holders
+    /// are paid out of the collateral at the market's original address.
+    fn migrate_settle_and_claim(&self, ctx: &mut TestContext) {
+        let market_token_account = ctx.token_account(&self.key);
+
+        // the market's collateral can neither be dropped nor read from another token account
+        assert_program_error(
+            ctx.migrate_market(&self.key, None),
+            PredictionError::MissingMarketTokenAccount,
+        );
+        assert_program_error(
+            ctx.migrate_market(
+                &self.key,
+                Some(ctx.token_account(&self.pass_holder.pubkey())),
+            ),
+            anchor_lang::error::ErrorCode::ConstraintTokenOwner,
+        );
+
+        ctx.migrate_market(&self.key, Some(market_token_account))
+            .unwrap();
+
+        for trader in [&self.pass_holder, &self.mixed_holder] {
+            ctx.migrate_position(&TestContext::position_pda(&trader.pubkey(), &self.key))
+                .unwrap();
+        }
+
+        let market = ctx.account::<Market>(&self.key);
+
+        assert_eq!(market.version, MARKET_VERSION);
+        assert!(market.seeds == MarketSeeds::Legacy);
+        assert_eq!(market.total_collateral, LEGACY_COLLATERAL);
+        assert_eq!(market.outcome_collateral[0], LEGACY_COLLATERAL * 3 / 4);
+        assert_eq!(market.outcome_collateral[1], LEGACY_COLLATERAL / 4);
+
+        ctx.set_time(ctx.publish_time());
+        ctx.settle(&self.key).unwrap();
+
+        assert!(ctx.account::<Market>(&self.key).state == MarketState::Passed);
+
+        let mut winnings = Vec::new();
+
+        for trader in [&self.pass_holder, &self.mixed_holder] {
+            let token_account = ctx.token_account(&trader.pubkey());
+            let before = ctx.token_balance(&token_account);
+
+            ctx.claim(trader, &self.key).unwrap();
+            winnings.push(ctx.token_balance(&token_account) - before);
+        }
+
+        // payouts round down by at most one base unit
+        assert!(winnings[0].abs_diff(LEGACY_COLLATERAL * 2 / 3) <= 1);
+        assert!(winnings[1].abs_diff(LEGACY_COLLATERAL / 3) <= 1);
+    }
+}
+
+#[test]
+#[ignore = "requires the program built by `anchor build`"]
+fn v1_config_is_rejected_until_migrated() {
+    let mut ctx = TestContext::new();
+    let resolve_ts = ctx.now() + 60 * 60;
+
+    set_v1_config(&mut ctx);
+
+    let err = ctx.create_market(resolve_ts, 100.0).unwrap_err();
+    assert_program_error(Err(err), PredictionError::AccountNotMigrated);
+
+    ctx.migrate_config().unwrap();
+
+    let config = ctx.account::<Config>(&TestContext::config_pda());
+
+    assert_eq!(config.version, CONFIG_VERSION);
+    assert_eq!(config.authority, ctx.authority.pubkey());
+    assert_eq!(config.fee_bps, FEE_BPS);
+    assert_eq!(config.mint, ctx.mint);
+    assert_eq!(config.min_market_duration, DEFAULT_MIN_MARKET_DURATION);
+    assert_eq!(config.max_market_duration, DEFAULT_MAX_MARKET_DURATION);
+    assert_eq!(config.market_count, 0);
+    assert_eq!(config.proposal_bond, 10_u64.pow(DECIMALS.into()));
+    assert_eq!(config.dispute_window, DEFAULT_DISPUTE_WINDOW);
+
+    ctx.create_market(resolve_ts, 100.0).unwrap();
+}
+
+#[test]
+#[ignore = "requires the program built by `anchor build`"]
+fn migrating_a_current_config_is_rejected() {
+    let mut ctx = TestContext::new();
+
+    ctx.initialize_config().unwrap();
+
+    assert_program_error(
+        ctx.migrate_config(),
+        PredictionError::AccountAlreadyMigrated,
+    );
+}
+
+#[test]
+#[ignore = "requires the program built by `anchor build`"]
+fn legacy_market_pays_claims_at_its_original_address() {
+    let mut ctx = TestContext::new();
+    let legacy_market = LegacyMarket::new(&mut ctx);
+
+    ctx.set_program_account(
+        &legacy_market.key,
+        legacy_account_data(
+            Market::DISCRIMINATOR,
+            &MarketV0 {
+                resolve_ts: ctx.publish_time(),
+                pass_shares: 30,
+                fail_shares: 10,
+                state: MarketState::Initialized,
+                price_update_v2: ctx.oracle.key,
+                target_price: ctx.fixture_price() - 1.0,
+                bump: legacy_market.bump,
+                title: String::from("SOL above target"),
+            },
+        ),
+    );
+
+    assert_program_error(
+        ctx.settle(&legacy_market.key),
+        anchor_lang::error::ErrorCode::AccountDidNotDeserialize,
+    );
+
+    legacy_market.migrate_settle_and_claim(&mut ctx);
+}
+
+#[test]
+#[ignore = "requires the program built by `anchor build`"]
+fn v1_market_pays_claims_at_its_original_address() {
+    let mut ctx = TestContext::new();
+    let legacy_market = LegacyMarket::new(&mut ctx);
+    let config = ctx.account::<Config>(&TestContext::config_pda());
+
+    // v1 markets are the current layout with the fields appended since left zeroed
+    let mut data = Vec::new();
+    Market {
+        version: 1,
+        pass_shares: 30,
+        fail_shares: 10,
+        ..Market::new(
+            0,
+            ctx.publish_time(),
+            ctx.oracle.key,
+            ctx.fixture_price() - 1.0,
+            config.share_price,
+            legacy_market.bump,
+            String::from("SOL above target"),
+        )
+    }
+    .try_serialize(&mut data)
+    .unwrap();
+    ctx.set_program_account(&legacy_market.key, data);
+
+    // the seeds are only recorded on migration, until then the id seeds don't match
+    assert_program_error(
+        ctx.settle(&legacy_market.key),
+        anchor_lang::error::ErrorCode::ConstraintSeeds,
+    );
+
+    legacy_market.migrate_settle_and_claim(&mut ctx);
+}
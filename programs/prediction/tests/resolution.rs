@@ -0,0 +1,143 @@
+//! Optimistic resolution of oracle markets that missed their settlement window, sent through
+//! LiteSVM. Run `anchor build` and then `cargo test -- --ignored`.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{
+    assert_program_error, TestContext, DECIMALS, DISPUTE_WINDOW, PROPOSAL_BOND, TRADER_BALANCE,
+};
+use prediction::{
+    error::PredictionError, Market, MarketState, Resolution, ResolutionState, RESOLVE_TS_WINDOW,
+};
+use solana_signer::Signer;
+
+/// Creates a scalar market over `[100, 300]` resolving at the fixture's publish time, then lets
+/// its settlement window lapse so it settles as undecided.
+fn undecided_scalar_market() -> (TestContext, Pubkey) {
+    let mut ctx = TestContext::new();
+    let publish_time = ctx.publish_time();
+
+    ctx.set_time(publish_time - 60 * 60);
+    ctx.initialize_config().unwrap();
+
+    let market = ctx
+        .create_scalar_market(publish_time, 100.0, 300.0)
+        .unwrap();
+
+    ctx.set_time(publish_time + RESOLVE_TS_WINDOW + 1);
+    ctx.settle(&market).unwrap();
+
+    assert!(ctx.account::<Market>(&market).state == MarketState::Undecided);
+
+    (ctx, market)
+}
+
+#[test]
+#[ignore = "requires the program built by `anchor build`"]
+fn undecided_scalar_market_resolves_to_proposed_price() {
+    let (mut ctx, market) = undecided_scalar_market();
+    let proposer = ctx.trader();
+
+    assert_program_error(
+        ctx.propose(&proposer, &market, 0, None),
+        PredictionError::InvalidOutcome,
+    );
+
+    ctx.propose(&proposer, &market, 0, Some(250.0)).unwrap();
+
+    let resolution = ctx.account::<Resolution>(&TestContext::resolution_pda(&market));
+    assert_eq!(resolution.proposed_price, 250.0);
+
+    ctx.set_time(ctx.now() + DISPUTE_WINDOW);
+    ctx.finalize(&market, &proposer.pubkey(), None, None)
+        .unwrap();
+
+    let state = ctx.account::<Market>(&market);
+    let resolution = ctx.account::<Resolution>(&TestContext::resolution_pda(&market));
+
+    assert!(state.state == MarketState::Resolved);
+    assert_eq!(state.settled_price, 250.0);
+    assert_eq!(state.scalar_long_bps(), 7_500);
+    assert!(resolution.state == ResolutionState::Finalized);
+    assert_eq!(resolution.final_price, 250.0);
+    assert_eq!(
+        ctx.token_balance(&ctx.token_account(&proposer.pubkey())),
+        TRADER_BALANCE,
+        "the proposer's bond is returned"
+    );
+}
+
+#[test]
+#[ignore = "requires the program built by `anchor build`"]
+fn disputed_scalar_proposal_settles_to_authority_price() {
+    let (mut ctx, market) = undecided_scalar_market();
+    let proposer = ctx.trader();
+    let challenger = ctx.trader();
+
+    ctx.propose(&proposer, &market, 0, Some(250.0)).unwrap();
+    ctx.dispute(&challenger, &market).unwrap();
+
+    assert_program_error(
+        ctx.finalize(&market, &challenger.pubkey(), Some(0), None),
+        PredictionError::MissingDisputeOutcome,
+    );
+
+    ctx.finalize(&market, &challenger.pubkey(), None, Some(150.0))
+        .unwrap();
+
+    let state = ctx.account::<Market>(&market);
+
+    assert!(state.state == MarketState::Resolved);
+    assert_eq!(state.settled_price, 150.0);
+    assert_eq!(state.scalar_long_bps(), 2_500);
+    assert_eq!(
+        ctx.token_balance(&ctx.token_account(&challenger.pubkey())),
+        TRADER_BALANCE + PROPOSAL_BOND,
+        "the challenger is paid the proposer's bond"
+    );
+}
+
+#[test]
+#[ignore = "requires the program built by `anchor build`"]
+fn bonds_in_a_foreign_mint_are_rejected() {
+    let (mut ctx, market) = undecided_scalar_market();
+    let proposer = ctx.trader();
+    let challenger = ctx.trader();
+
+    ctx.propose(&proposer, &market, 0, Some(250.0)).unwrap();
+
+    // token accounts of every party in a mint other than the config's
+    let trading_mint = ctx.mint;
+    ctx.mint = ctx.create_mint(DECIMALS);
+
+    for owner in [
+        proposer.pubkey(),
+        challenger.pubkey(),
+        TestContext::resolution_pda(&market),
+    ] {
+        ctx.fund_token_account(&owner, TRADER_BALANCE);
+    }
+
+    assert_program_error(
+        ctx.dispute(&challenger, &market),
+        PredictionError::InvalidTradingMint,
+    );
+
+    ctx.set_time(ctx.now() + DISPUTE_WINDOW);
+
+    assert_program_error(
+        ctx.finalize(&market, &proposer.pubkey(), None, None),
+        PredictionError::InvalidTradingMint,
+    );
+
+    ctx.mint = trading_mint;
+    ctx.finalize(&market, &proposer.pubkey(), None, None)
+        .unwrap();
+
+    assert_eq!(
+        ctx.token_balance(&ctx.token_account(&proposer.pubkey())),
+        TRADER_BALANCE,
+        "the proposer's bond is returned in the trading mint"
+    );
+}
@@ -0,0 +1,69 @@
+//! `settle_market` sent through LiteSVM against the Pyth fixture rewritten to other prices and
+//! exponents. Run `anchor build` and then `cargo test -- --ignored`.
+
+mod common;
+
+use common::TestContext;
+use prediction::{Market, MarketState};
+
+/// Creates a market on `target_price` at the fixture's publish time, rewrites the fixture to
+/// `price * 10^exponent` and settles the market against it.
+fn settle(target_price: f64, price: i64, exponent: i32) -> Market {
+    let mut ctx = TestContext::new();
+    let publish_time = ctx.publish_time();
+
+    ctx.set_time(publish_time - 60 * 60);
+    ctx.initialize_config().unwrap();
+
+    let market = ctx.create_market(publish_time, target_price).unwrap();
+
+    ctx.set_fixture_price(price, exponent);
+    ctx.set_time(publish_time);
+    ctx.settle(&market).unwrap();
+
+    ctx.account::<Market>(&market)
+}
+
+#[test]
+#[ignore = "requires the program built by `anchor build`"]
+fn settles_against_negative_exponent() {
+    let cases = [
+        // (price, exponent, target, expected)
+        (19_114_841_996, -8, 191.14841996, MarketState::Passed),
+        (19_114_841_995, -8, 191.14841996, MarketState::Failed),
+        (150_000, -3, 150.0, MarketState::Passed),
+    ];
+
+    for (price, exponent, target_price, expected) in cases {
+        let market = settle(target_price, price, exponent);
+
+        assert!(
+            market.state == expected,
+            "price {price}e{exponent} against target {target_price}"
+        );
+        assert_eq!(market.settled_price, price as f64 / 10_f64.powi(-exponent));
+    }
+}
+
+#[test]
+#[ignore = "requires the program built by `anchor build`"]
+fn settles_against_zero_and_positive_exponents() {
+    let cases = [
+        // (price, exponent, target, settled price, expected)
+        (150, 0, 150.0, 150.0, MarketState::Passed),
+        (149, 0, 150.0, 149.0, MarketState::Failed),
+        (15, 1, 150.0, 150.0, MarketState::Passed),
+        (1, 2, 150.0, 100.0, MarketState::Failed),
+        (2, 2, 150.0, 200.0, MarketState::Passed),
+    ];
+
+    for (price, exponent, target_price, settled_price, expected) in cases {
+        let market = settle(target_price, price, exponent);
+
+        assert!(
+            market.state == expected,
+            "price {price}e{exponent} against target {target_price}"
+        );
+        assert_eq!(market.settled_price, settled_price);
+    }
+}
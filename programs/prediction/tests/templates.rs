@@ -0,0 +1,39 @@
+//! `spawn_from_template` rolling markets over through LiteSVM against the Pyth fixture. Run
+//! `anchor build` and then `cargo test -- --ignored`.
+
+mod common;
+
+use common::{TestContext, MIN_MARKET_DURATION};
+use prediction::Market;
+
+#[test]
+#[ignore = "requires the program built by `anchor build`"]
+fn late_spawn_skips_to_the_next_slot_meeting_the_minimum_duration() {
+    let mut ctx = TestContext::new();
+    let publish_time = ctx.publish_time();
+
+    ctx.set_time(publish_time - MIN_MARKET_DURATION);
+    ctx.initialize_config().unwrap();
+
+    let market_template = ctx
+        .create_market_template(MIN_MARKET_DURATION, 150.0)
+        .unwrap();
+    let first = ctx.spawn_from_template(&market_template, None).unwrap();
+
+    assert_eq!(ctx.account::<Market>(&first).resolve_ts, publish_time);
+
+    ctx.set_time(publish_time);
+    ctx.settle(&first).unwrap();
+
+    // the next slot is a second away, too close to meet the minimum duration
+    ctx.set_time(publish_time + MIN_MARKET_DURATION - 1);
+
+    let second = ctx
+        .spawn_from_template(&market_template, Some(first))
+        .unwrap();
+
+    assert_eq!(
+        ctx.account::<Market>(&second).resolve_ts,
+        publish_time + 2 * MIN_MARKET_DURATION
+    );
+}
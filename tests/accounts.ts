@@ -22,3 +22,38 @@ export async function fetchPositionAcc(
 ) {
   return program.account.position.fetchNullable(positionPda);
 }
+
+export async function fetchMarketMetadataAcc(
+  program: Program<Prediction>,
+  marketMetadataPda: PublicKey
+) {
+  return program.account.marketMetadata.fetchNullable(marketMetadataPda);
+}
+
+export async function fetchMarketSeriesAcc(
+  program: Program<Prediction>,
+  marketSeriesPda: PublicKey
+) {
+  return program.account.marketSeries.fetchNullable(marketSeriesPda);
+}
+
+export async function fetchMarketTemplateAcc(
+  program: Program<Prediction>,
+  marketTemplatePda: PublicKey
+) {
+  return program.account.marketTemplate.fetchNullable(marketTemplatePda);
+}
+
+export async function fetchResolutionAcc(
+  program: Program<Prediction>,
+  resolutionPda: PublicKey
+) {
+  return program.account.resolution.fetchNullable(resolutionPda);
+}
+
+export async function fetchPriceSamplesAcc(
+  program: Program<Prediction>,
+  priceSamplesPda: PublicKey
+) {
+  return program.account.priceSamples.fetchNullable(priceSamplesPda);
+}
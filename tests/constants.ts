@@ -8,5 +8,15 @@ export const PYTH_SOLANA_RECEIVER_PROGRAM_ID = new PublicKey(
 export const SOL_USD_PRICE_UPDATE_V2 = new PublicKey(
   "7UVimffxr9ow1uXYxsr4LHAcV58mLzhmwaeKvJ1pjLiE"
 );
+export const SWITCHBOARD_ON_DEMAND_PROGRAM_ID = new PublicKey(
+  "SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv"
+);
+export const SOL_USD_SWITCHBOARD_PULL_FEED = new PublicKey(
+  "4F516VBwKZ811gKnK8FTWaAE1WJHRLzreP22nuPtaRfF"
+);
 export const USDC_MINT = PublicKey.unique();
 export const USDC_MINT_DECIMALS = 6;
+export const MIN_MARKET_DURATION = 60 * 5; // 5 minutes
+export const MAX_MARKET_DURATION = 60 * 60 * 24 * 30; // 30 days
+export const PROPOSAL_BOND = 10 * 10 ** 6; // 10 USDC
+export const DISPUTE_WINDOW = 60 * 60 * 2; // 2 hours
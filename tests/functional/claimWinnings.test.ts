@@ -12,7 +12,12 @@ import {
   initUsdcAta,
 } from "../setup";
 import { getConfigPda, getMarketPda, getPositionPda } from "../pda";
+import { fetchMarketAcc } from "../accounts";
 import {
+  DISPUTE_WINDOW,
+  MAX_MARKET_DURATION,
+  MIN_MARKET_DURATION,
+  PROPOSAL_BOND,
   SOL_USD_PRICE_UPDATE_V2,
   USDC_MINT,
   USDC_MINT_DECIMALS,
@@ -56,6 +61,10 @@ describe("claimWinnings", () => {
     await program.methods
       .initializeConfig({
         feeBps: 10,
+        minMarketDuration: new BN(MIN_MARKET_DURATION),
+        maxMarketDuration: new BN(MAX_MARKET_DURATION),
+        proposalBond: new BN(PROPOSAL_BOND),
+        disputeWindow: new BN(DISPUTE_WINDOW),
       })
       .accounts({
         authority: configAuthority.publicKey,
@@ -83,7 +92,7 @@ describe("claimWinnings", () => {
       .signers([configAuthority])
       .rpc();
 
-    marketPda = getMarketPda(SOL_USD_PRICE_UPDATE_V2, resolveTs);
+    marketPda = getMarketPda(0);
 
     await program.methods
       .openPosition()
@@ -205,6 +214,13 @@ describe("claimWinnings", () => {
 
     expect(preMarketAtaBal).toBeGreaterThan(postMarketAtaBal);
 
+    // the only PASS holder is paid all collateral in the market
+    const marketAcc = await fetchMarketAcc(program, marketPda);
+
+    expect(Number(preMarketAtaBal - postMarketAtaBal)).toBe(
+      marketAcc.totalCollateral.toNumber()
+    );
+
     const postPassPositionAuthorityAtaAcc = await getAccount(
       provider.connection,
       prePassPositionAuthorityAta,
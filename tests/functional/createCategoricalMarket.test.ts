@@ -0,0 +1,134 @@
+import { BN, Program } from "@coral-xyz/anchor";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { beforeEach, describe, expect, test } from "bun:test";
+import { LiteSVM } from "litesvm";
+import { Prediction } from "../../target/types/prediction";
+import { Keypair } from "@solana/web3.js";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+import { getMarketPda } from "../pda";
+import { fetchMarketAcc } from "../accounts";
+import {
+  DISPUTE_WINDOW,
+  MAX_MARKET_DURATION,
+  MIN_MARKET_DURATION,
+  PROPOSAL_BOND,
+  SOL_USD_PRICE_UPDATE_V2,
+  USDC_MINT,
+} from "../constants";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+describe("createCategoricalMarket", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<Prediction>;
+  };
+
+  const configAuthority = Keypair.generate();
+
+  const marketOpenPeriod = 60 * 60 * 24; // 1 day
+  const title = "Where will SOL close in 24 hours?";
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      {
+        pubkey: configAuthority.publicKey,
+        account: fundedSystemAccountInfo(),
+      },
+    ]));
+
+    await program.methods
+      .initializeConfig({
+        feeBps: 10,
+        minMarketDuration: new BN(MIN_MARKET_DURATION),
+        maxMarketDuration: new BN(MAX_MARKET_DURATION),
+        proposalBond: new BN(PROPOSAL_BOND),
+        disputeWindow: new BN(DISPUTE_WINDOW),
+      })
+      .accounts({
+        authority: configAuthority.publicKey,
+        mint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([configAuthority])
+      .rpc();
+  });
+
+  test("creates a categorical market", async () => {
+    const now = litesvm.getClock().unixTimestamp;
+    const resolveTs = Number(now) + marketOpenPeriod;
+    const bounds = [120, 140, 160, 180];
+
+    await program.methods
+      .createCategoricalMarket({
+        resolveTs: new BN(resolveTs),
+        bounds,
+        title,
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tradingMint: USDC_MINT,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const marketAcc = await fetchMarketAcc(program, getMarketPda(0));
+
+    expect(marketAcc.kind).toEqual({ categorical: {} });
+    expect(marketAcc.outcomeCount).toBe(bounds.length + 1);
+    expect(marketAcc.bounds.slice(0, bounds.length)).toStrictEqual(bounds);
+    expect(marketAcc.outcomeShares.every((shares) => shares.isZero())).toBe(
+      true
+    );
+    expect(marketAcc.resolveTs.toNumber()).toBe(resolveTs);
+    expect(marketAcc.title).toBe(title);
+  });
+
+  test("throws if bounds are not strictly increasing", async () => {
+    const now = litesvm.getClock().unixTimestamp;
+
+    try {
+      await program.methods
+        .createCategoricalMarket({
+          resolveTs: new BN(Number(now) + marketOpenPeriod),
+          bounds: [140, 120],
+          title,
+        })
+        .accountsPartial({
+          authority: configAuthority.publicKey,
+          priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          tradingMint: USDC_MINT,
+        })
+        .signers([configAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidOutcomeBounds");
+    }
+  });
+
+  test("throws if there are too many outcomes", async () => {
+    const now = litesvm.getClock().unixTimestamp;
+
+    try {
+      await program.methods
+        .createCategoricalMarket({
+          resolveTs: new BN(Number(now) + marketOpenPeriod),
+          bounds: [100, 110, 120, 130, 140, 150, 160, 170],
+          title,
+        })
+        .accountsPartial({
+          authority: configAuthority.publicKey,
+          priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          tradingMint: USDC_MINT,
+        })
+        .signers([configAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidOutcomeBounds");
+    }
+  });
+});
@@ -0,0 +1,106 @@
+import { BN, Program } from "@coral-xyz/anchor";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { beforeEach, describe, expect, test } from "bun:test";
+import { LiteSVM } from "litesvm";
+import { Prediction } from "../../target/types/prediction";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+import { getMarketPda } from "../pda";
+import { fetchMarketAcc } from "../accounts";
+import {
+  DISPUTE_WINDOW,
+  MAX_MARKET_DURATION,
+  MIN_MARKET_DURATION,
+  PROPOSAL_BOND,
+  USDC_MINT,
+} from "../constants";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+describe("createManualMarket", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<Prediction>;
+  };
+
+  const [configAuthority, resolver] = Array.from({ length: 2 }, () =>
+    Keypair.generate()
+  );
+
+  const marketOpenPeriod = 60 * 60 * 24; // 1 day
+  const title = "Will the incumbent win the election?";
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      {
+        pubkey: configAuthority.publicKey,
+        account: fundedSystemAccountInfo(),
+      },
+    ]));
+
+    await program.methods
+      .initializeConfig({
+        feeBps: 10,
+        minMarketDuration: new BN(MIN_MARKET_DURATION),
+        maxMarketDuration: new BN(MAX_MARKET_DURATION),
+        proposalBond: new BN(PROPOSAL_BOND),
+        disputeWindow: new BN(DISPUTE_WINDOW),
+      })
+      .accounts({
+        authority: configAuthority.publicKey,
+        mint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([configAuthority])
+      .rpc();
+  });
+
+  test("creates a manually resolved market", async () => {
+    const now = litesvm.getClock().unixTimestamp;
+    const resolveTs = Number(now) + marketOpenPeriod;
+
+    await program.methods
+      .createManualMarket({
+        resolveTs: new BN(resolveTs),
+        resolver: resolver.publicKey,
+        title,
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tradingMint: USDC_MINT,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const marketAcc = await fetchMarketAcc(program, getMarketPda(0));
+
+    expect(marketAcc.resolution).toEqual({ manual: {} });
+    expect(marketAcc.resolver).toStrictEqual(resolver.publicKey);
+    expect(marketAcc.priceUpdateV2).toStrictEqual(PublicKey.default);
+    expect(marketAcc.resolveTs.toNumber()).toBe(resolveTs);
+    expect(marketAcc.title).toBe(title);
+  });
+
+  test("throws if resolver is the default pubkey", async () => {
+    const now = litesvm.getClock().unixTimestamp;
+
+    try {
+      await program.methods
+        .createManualMarket({
+          resolveTs: new BN(Number(now) + marketOpenPeriod),
+          resolver: PublicKey.default,
+          title,
+        })
+        .accountsPartial({
+          authority: configAuthority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          tradingMint: USDC_MINT,
+        })
+        .signers([configAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidResolver");
+    }
+  });
+});
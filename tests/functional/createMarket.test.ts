@@ -4,10 +4,18 @@ import { beforeEach, describe, expect, test } from "bun:test";
 import { LiteSVM } from "litesvm";
 import { Prediction } from "../../target/types/prediction";
 import { Keypair } from "@solana/web3.js";
-import { fundedSystemAccountInfo, getSetup } from "../setup";
-import { getMarketPda } from "../pda";
-import { fetchMarketAcc } from "../accounts";
-import { SOL_USD_PRICE_UPDATE_V2, USDC_MINT } from "../constants";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+import { getConfigPda, getMarketPda } from "../pda";
+import { fetchConfigAcc, fetchMarketAcc } from "../accounts";
+import {
+  DISPUTE_WINDOW,
+  MAX_MARKET_DURATION,
+  MIN_MARKET_DURATION,
+  PROPOSAL_BOND,
+  SOL_USD_PRICE_UPDATE_V2,
+  USDC_MINT,
+  USDC_MINT_DECIMALS,
+} from "../constants";
 import {
   getAssociatedTokenAddressSync,
   TOKEN_PROGRAM_ID,
@@ -33,6 +41,10 @@ describe("createMarket", () => {
     await program.methods
       .initializeConfig({
         feeBps: 10,
+        minMarketDuration: new BN(MIN_MARKET_DURATION),
+        maxMarketDuration: new BN(MAX_MARKET_DURATION),
+        proposalBond: new BN(PROPOSAL_BOND),
+        disputeWindow: new BN(DISPUTE_WINDOW),
       })
       .accounts({
         authority: configAuthority.publicKey,
@@ -64,12 +76,14 @@ describe("createMarket", () => {
       .signers([configAuthority])
       .rpc();
 
-    const marketPda = getMarketPda(priceUpdateV2, resolveTs);
+    const marketPda = getMarketPda(0);
     const marketAcc = await fetchMarketAcc(program, marketPda);
 
+    expect(marketAcc.id.toNumber()).toBe(0);
     expect(marketAcc.resolveTs.toNumber()).toBe(resolveTs);
     expect(marketAcc.priceUpdateV2).toStrictEqual(priceUpdateV2);
     expect(marketAcc.targetPrice).toBe(targetPrice);
+    expect(marketAcc.sharePrice.toNumber()).toBe(10 ** USDC_MINT_DECIMALS);
 
     const marketAta = getAssociatedTokenAddressSync(
       USDC_MINT,
@@ -81,4 +95,153 @@ describe("createMarket", () => {
 
     expect(marketAtaAcc).not.toBeNull();
   });
+
+  test("creates markets on the same feed and expiry", async () => {
+    const now = litesvm.getClock().unixTimestamp;
+    const resolveTs = Number(now) + 60 * 60 * 24; // 1 day from now
+    const targetPrices = [150, 160];
+
+    for (const targetPrice of targetPrices) {
+      await program.methods
+        .createMarket({
+          resolveTs: new BN(resolveTs),
+          targetPrice,
+          title: `Will SOL reach $${targetPrice} in 24 hours?`,
+        })
+        .accountsPartial({
+          authority: configAuthority.publicKey,
+          priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          tradingMint: USDC_MINT,
+        })
+        .signers([configAuthority])
+        .rpc();
+    }
+
+    for (const [id, targetPrice] of targetPrices.entries()) {
+      const marketAcc = await fetchMarketAcc(program, getMarketPda(id));
+
+      expect(marketAcc.id.toNumber()).toBe(id);
+      expect(marketAcc.targetPrice).toBe(targetPrice);
+    }
+
+    const configAcc = await fetchConfigAcc(program, getConfigPda());
+
+    expect(configAcc.marketCount.toNumber()).toBe(targetPrices.length);
+  });
+
+  test("throws if resolve timestamp is in the past", async () => {
+    const now = litesvm.getClock().unixTimestamp;
+
+    try {
+      await program.methods
+        .createMarket({
+          resolveTs: new BN(Number(now) - 1),
+          targetPrice: 150,
+          title: "Will SOL reach $150?",
+        })
+        .accountsPartial({
+          authority: configAuthority.publicKey,
+          priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          tradingMint: USDC_MINT,
+        })
+        .signers([configAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "ResolveTsInPast");
+    }
+  });
+
+  test("throws if market duration exceeds the maximum", async () => {
+    const now = litesvm.getClock().unixTimestamp;
+
+    try {
+      await program.methods
+        .createMarket({
+          resolveTs: new BN(Number(now) + MAX_MARKET_DURATION + 1),
+          targetPrice: 150,
+          title: "Will SOL reach $150?",
+        })
+        .accountsPartial({
+          authority: configAuthority.publicKey,
+          priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          tradingMint: USDC_MINT,
+        })
+        .signers([configAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "MarketDurationTooLong");
+    }
+  });
+
+  test("throws if title is too long", async () => {
+    const now = litesvm.getClock().unixTimestamp;
+
+    try {
+      await program.methods
+        .createMarket({
+          resolveTs: new BN(Number(now) + 60 * 60 * 24),
+          targetPrice: 150,
+          title: "a".repeat(129),
+        })
+        .accountsPartial({
+          authority: configAuthority.publicKey,
+          priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          tradingMint: USDC_MINT,
+        })
+        .signers([configAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "TitleTooLong");
+    }
+  });
+
+  test("throws if target price is not positive", async () => {
+    const now = litesvm.getClock().unixTimestamp;
+
+    try {
+      await program.methods
+        .createMarket({
+          resolveTs: new BN(Number(now) + 60 * 60 * 24),
+          targetPrice: -150,
+          title: "Will SOL reach $-150?",
+        })
+        .accountsPartial({
+          authority: configAuthority.publicKey,
+          priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          tradingMint: USDC_MINT,
+        })
+        .signers([configAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidTargetPrice");
+    }
+  });
+
+  test("throws if price account is not a supported oracle", async () => {
+    const now = litesvm.getClock().unixTimestamp;
+
+    try {
+      await program.methods
+        .createMarket({
+          resolveTs: new BN(Number(now) + 60 * 60 * 24),
+          targetPrice: 150,
+          title: "Will SOL reach $150 in 24 hours?",
+        })
+        .accountsPartial({
+          authority: configAuthority.publicKey,
+          priceUpdateV2: USDC_MINT,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          tradingMint: USDC_MINT,
+        })
+        .signers([configAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidPriceUpdateV2");
+    }
+  });
 });
@@ -0,0 +1,226 @@
+import { BN, Program } from "@coral-xyz/anchor";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { beforeEach, describe, expect, test } from "bun:test";
+import { LiteSVM } from "litesvm";
+import { Prediction } from "../../target/types/prediction";
+import { Keypair } from "@solana/web3.js";
+import {
+  expectAnchorError,
+  fundedSystemAccountInfo,
+  getSetup,
+  initUsdcAta,
+} from "../setup";
+import { getMarketPda, getMarketSeriesPda } from "../pda";
+import { fetchMarketAcc, fetchMarketSeriesAcc } from "../accounts";
+import {
+  DISPUTE_WINDOW,
+  MAX_MARKET_DURATION,
+  MIN_MARKET_DURATION,
+  PROPOSAL_BOND,
+  SOL_USD_PRICE_UPDATE_V2,
+  USDC_MINT,
+} from "../constants";
+import {
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+
+describe("createMarketSeries", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<Prediction>;
+  };
+
+  const configAuthority = Keypair.generate();
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      {
+        pubkey: configAuthority.publicKey,
+        account: fundedSystemAccountInfo(),
+      },
+    ]));
+
+    await program.methods
+      .initializeConfig({
+        feeBps: 10,
+        minMarketDuration: new BN(MIN_MARKET_DURATION),
+        maxMarketDuration: new BN(MAX_MARKET_DURATION),
+        proposalBond: new BN(PROPOSAL_BOND),
+        disputeWindow: new BN(DISPUTE_WINDOW),
+      })
+      .accounts({
+        authority: configAuthority.publicKey,
+        mint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([configAuthority])
+      .rpc();
+  });
+
+  test("creates a market series", async () => {
+    const now = litesvm.getClock().unixTimestamp;
+    const resolveTs = Number(now) + 60 * 60 * 24; // 1 day from now
+    const strikes = [140, 150, 160].map((targetPrice) => ({
+      targetPrice,
+      title: `Will SOL reach $${targetPrice} in 24 hours?`,
+    }));
+    const marketPdas = strikes.map((_, id) => getMarketPda(id));
+
+    await program.methods
+      .createMarketSeries({
+        resolveTs: new BN(resolveTs),
+        strikes,
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tradingMint: USDC_MINT,
+      })
+      .remainingAccounts(
+        marketPdas.flatMap((marketPda) => [
+          { pubkey: marketPda, isSigner: false, isWritable: true },
+          {
+            pubkey: getAssociatedTokenAddressSync(
+              USDC_MINT,
+              marketPda,
+              true,
+              TOKEN_PROGRAM_ID
+            ),
+            isSigner: false,
+            isWritable: true,
+          },
+        ])
+      )
+      .signers([configAuthority])
+      .rpc();
+
+    const marketSeriesPda = getMarketSeriesPda(0);
+    const marketSeriesAcc = await fetchMarketSeriesAcc(
+      program,
+      marketSeriesPda
+    );
+
+    expect(marketSeriesAcc.resolveTs.toNumber()).toBe(resolveTs);
+    expect(marketSeriesAcc.markets).toStrictEqual(marketPdas);
+
+    for (const [id, { targetPrice }] of strikes.entries()) {
+      const marketAcc = await fetchMarketAcc(program, marketPdas[id]);
+
+      expect(marketAcc.targetPrice).toBe(targetPrice);
+      expect(marketAcc.resolveTs.toNumber()).toBe(resolveTs);
+      expect(marketAcc.series).toStrictEqual(marketSeriesPda);
+
+      const marketAta = getAssociatedTokenAddressSync(
+        USDC_MINT,
+        marketPdas[id],
+        true,
+        TOKEN_PROGRAM_ID
+      );
+
+      expect(litesvm.getAccount(marketAta)).not.toBeNull();
+    }
+  });
+
+  test("creates a market series when a market address was sent lamports", async () => {
+    const now = litesvm.getClock().unixTimestamp;
+    const marketPda = getMarketPda(0);
+
+    litesvm.setAccount(marketPda, fundedSystemAccountInfo());
+
+    await program.methods
+      .createMarketSeries({
+        resolveTs: new BN(Number(now) + 60 * 60 * 24),
+        strikes: [{ targetPrice: 150, title: "Will SOL reach $150?" }],
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tradingMint: USDC_MINT,
+      })
+      .remainingAccounts([
+        { pubkey: marketPda, isSigner: false, isWritable: true },
+        {
+          pubkey: getAssociatedTokenAddressSync(
+            USDC_MINT,
+            marketPda,
+            true,
+            TOKEN_PROGRAM_ID
+          ),
+          isSigner: false,
+          isWritable: true,
+        },
+      ])
+      .signers([configAuthority])
+      .rpc();
+
+    const marketAcc = await fetchMarketAcc(program, marketPda);
+
+    expect(marketAcc.targetPrice).toBe(150);
+    expect(marketAcc.series).toStrictEqual(getMarketSeriesPda(0));
+  });
+
+  test("creates a market series when a market token account already exists", async () => {
+    const now = litesvm.getClock().unixTimestamp;
+    const marketPda = getMarketPda(0);
+
+    initUsdcAta(litesvm, marketPda, 0);
+
+    await program.methods
+      .createMarketSeries({
+        resolveTs: new BN(Number(now) + 60 * 60 * 24),
+        strikes: [{ targetPrice: 150, title: "Will SOL reach $150?" }],
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tradingMint: USDC_MINT,
+      })
+      .remainingAccounts([
+        { pubkey: marketPda, isSigner: false, isWritable: true },
+        {
+          pubkey: getAssociatedTokenAddressSync(
+            USDC_MINT,
+            marketPda,
+            true,
+            TOKEN_PROGRAM_ID
+          ),
+          isSigner: false,
+          isWritable: true,
+        },
+      ])
+      .signers([configAuthority])
+      .rpc();
+
+    const marketAcc = await fetchMarketAcc(program, marketPda);
+
+    expect(marketAcc.targetPrice).toBe(150);
+    expect(marketAcc.series).toStrictEqual(getMarketSeriesPda(0));
+  });
+
+  test("throws if remaining accounts do not match strikes", async () => {
+    const now = litesvm.getClock().unixTimestamp;
+
+    try {
+      await program.methods
+        .createMarketSeries({
+          resolveTs: new BN(Number(now) + 60 * 60 * 24),
+          strikes: [{ targetPrice: 150, title: "Will SOL reach $150?" }],
+        })
+        .accountsPartial({
+          authority: configAuthority.publicKey,
+          priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          tradingMint: USDC_MINT,
+        })
+        .signers([configAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidSeriesAccounts");
+    }
+  });
+});
@@ -0,0 +1,172 @@
+import { BN, Program } from "@coral-xyz/anchor";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { beforeEach, describe, expect, test } from "bun:test";
+import { LiteSVM } from "litesvm";
+import { Prediction } from "../../target/types/prediction";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+import { getConfigPda, getMarketTemplatePda } from "../pda";
+import { fetchConfigAcc, fetchMarketTemplateAcc } from "../accounts";
+import {
+  DISPUTE_WINDOW,
+  MAX_MARKET_DURATION,
+  MIN_MARKET_DURATION,
+  PROPOSAL_BOND,
+  SOL_USD_PRICE_UPDATE_V2,
+  USDC_MINT,
+} from "../constants";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+describe("createMarketTemplate", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<Prediction>;
+  };
+
+  const [configAuthority, nonAuthority] = Array.from({ length: 2 }, () =>
+    Keypair.generate()
+  );
+
+  const duration = 60 * 60; // 1 hour
+  const title = "Will SOL close the hour above the last settlement?";
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      ...[configAuthority, nonAuthority].map((kp) => {
+        return {
+          pubkey: kp.publicKey,
+          account: fundedSystemAccountInfo(),
+        };
+      }),
+    ]));
+
+    await program.methods
+      .initializeConfig({
+        feeBps: 10,
+        minMarketDuration: new BN(MIN_MARKET_DURATION),
+        maxMarketDuration: new BN(MAX_MARKET_DURATION),
+        proposalBond: new BN(PROPOSAL_BOND),
+        disputeWindow: new BN(DISPUTE_WINDOW),
+      })
+      .accounts({
+        authority: configAuthority.publicKey,
+        mint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([configAuthority])
+      .rpc();
+  });
+
+  test("creates a market template", async () => {
+    await program.methods
+      .createMarketTemplate({
+        condition: { above: {} },
+        duration: new BN(duration),
+        feeBps: 25,
+        strikeRule: { lastSettlementOffset: {} },
+        targetPrice: 150,
+        offsetBps: 100,
+        title,
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const marketTemplateAcc = await fetchMarketTemplateAcc(
+      program,
+      getMarketTemplatePda(0)
+    );
+
+    expect(marketTemplateAcc.id.toNumber()).toBe(0);
+    expect(marketTemplateAcc.priceUpdateV2).toStrictEqual(
+      SOL_USD_PRICE_UPDATE_V2
+    );
+    expect(marketTemplateAcc.condition).toEqual({ above: {} });
+    expect(marketTemplateAcc.duration.toNumber()).toBe(duration);
+    expect(marketTemplateAcc.hasFeeOverride).toBe(true);
+    expect(marketTemplateAcc.feeBps).toBe(25);
+    expect(marketTemplateAcc.strikeRule).toEqual({ lastSettlementOffset: {} });
+    expect(marketTemplateAcc.targetPrice).toBe(150);
+    expect(marketTemplateAcc.offsetBps).toBe(100);
+    expect(marketTemplateAcc.lastMarket).toStrictEqual(PublicKey.default);
+    expect(marketTemplateAcc.spawnCount.toNumber()).toBe(0);
+    expect(marketTemplateAcc.title).toBe(title);
+
+    const configAcc = await fetchConfigAcc(program, getConfigPda());
+
+    expect(configAcc.templateCount.toNumber()).toBe(1);
+  });
+
+  test("throws if authority is not config authority", async () => {
+    try {
+      await program.methods
+        .createMarketTemplate({
+          condition: { above: {} },
+          duration: new BN(duration),
+          feeBps: null,
+          strikeRule: { fixed: {} },
+          targetPrice: 150,
+          offsetBps: 0,
+          title,
+        })
+        .accountsPartial({
+          authority: nonAuthority.publicKey,
+          priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        })
+        .signers([nonAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidConfigAuthority");
+    }
+  });
+
+  test("throws if duration is shorter than config minimum", async () => {
+    try {
+      await program.methods
+        .createMarketTemplate({
+          condition: { above: {} },
+          duration: new BN(MIN_MARKET_DURATION - 1),
+          feeBps: null,
+          strikeRule: { fixed: {} },
+          targetPrice: 150,
+          offsetBps: 0,
+          title,
+        })
+        .accountsPartial({
+          authority: configAuthority.publicKey,
+          priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        })
+        .signers([configAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "MarketDurationTooShort");
+    }
+  });
+
+  test("throws if strike offset is -100% or lower", async () => {
+    try {
+      await program.methods
+        .createMarketTemplate({
+          condition: { above: {} },
+          duration: new BN(duration),
+          feeBps: null,
+          strikeRule: { lastSettlementOffset: {} },
+          targetPrice: 150,
+          offsetBps: -10_000,
+          title,
+        })
+        .accountsPartial({
+          authority: configAuthority.publicKey,
+          priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        })
+        .signers([configAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidStrikeOffset");
+    }
+  });
+});
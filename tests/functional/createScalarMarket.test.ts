@@ -0,0 +1,224 @@
+import { BN, Program } from "@coral-xyz/anchor";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { beforeEach, describe, expect, test } from "bun:test";
+import { LiteSVM } from "litesvm";
+import { Prediction } from "../../target/types/prediction";
+import { Keypair } from "@solana/web3.js";
+import {
+  expectAnchorError,
+  forwardTime,
+  fundedSystemAccountInfo,
+  getSetup,
+  initUsdcAta,
+} from "../setup";
+import { getMarketPda, getPositionPda } from "../pda";
+import { fetchMarketAcc } from "../accounts";
+import {
+  DISPUTE_WINDOW,
+  MAX_MARKET_DURATION,
+  MIN_MARKET_DURATION,
+  PROPOSAL_BOND,
+  SOL_USD_PRICE_UPDATE_V2,
+  USDC_MINT,
+  USDC_MINT_DECIMALS,
+} from "../constants";
+import {
+  getAccount,
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+
+describe("createScalarMarket", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<Prediction>;
+  };
+
+  const [configAuthority, longPositionAuthority, shortPositionAuthority] =
+    Array.from({ length: 3 }, () => Keypair.generate());
+
+  const initAtaBal = 100 * 10 ** USDC_MINT_DECIMALS;
+  const marketOpenPeriod = 60 * 60 * 24; // 1 day
+  const lower = 100;
+  const upper = 200;
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      ...[configAuthority, longPositionAuthority, shortPositionAuthority].map(
+        (kp) => {
+          return {
+            pubkey: kp.publicKey,
+            account: fundedSystemAccountInfo(),
+          };
+        }
+      ),
+    ]));
+
+    for (const kp of [longPositionAuthority, shortPositionAuthority]) {
+      initUsdcAta(litesvm, kp.publicKey, initAtaBal);
+    }
+
+    await program.methods
+      .initializeConfig({
+        feeBps: 10,
+        minMarketDuration: new BN(MIN_MARKET_DURATION),
+        maxMarketDuration: new BN(MAX_MARKET_DURATION),
+        proposalBond: new BN(PROPOSAL_BOND),
+        disputeWindow: new BN(DISPUTE_WINDOW),
+      })
+      .accounts({
+        authority: configAuthority.publicKey,
+        mint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([configAuthority])
+      .rpc();
+  });
+
+  async function createScalarMarket() {
+    const now = litesvm.getClock().unixTimestamp;
+
+    await program.methods
+      .createScalarMarket({
+        resolveTs: new BN(Number(now) + marketOpenPeriod),
+        lower,
+        upper,
+        title: "Where will SOL close between $100 and $200?",
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tradingMint: USDC_MINT,
+      })
+      .signers([configAuthority])
+      .rpc();
+  }
+
+  test("creates a scalar market", async () => {
+    await createScalarMarket();
+
+    const marketAcc = await fetchMarketAcc(program, getMarketPda(0));
+
+    expect(marketAcc.kind).toEqual({ scalar: {} });
+    expect(marketAcc.bounds.slice(0, 2)).toStrictEqual([lower, upper]);
+  });
+
+  test("throws if lower bound is not below upper bound", async () => {
+    const now = litesvm.getClock().unixTimestamp;
+
+    try {
+      await program.methods
+        .createScalarMarket({
+          resolveTs: new BN(Number(now) + marketOpenPeriod),
+          lower: upper,
+          upper: lower,
+          title: "Where will SOL close between $100 and $200?",
+        })
+        .accountsPartial({
+          authority: configAuthority.publicKey,
+          priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          tradingMint: USDC_MINT,
+        })
+        .signers([configAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidOutcomeBounds");
+    }
+  });
+
+  test("pays LONG shares linearly between the bounds", async () => {
+    await createScalarMarket();
+
+    const marketPda = getMarketPda(0);
+
+    for (const [kp, isPass] of [
+      [longPositionAuthority, true],
+      [shortPositionAuthority, false],
+    ] as const) {
+      await program.methods
+        .openPosition()
+        .accountsPartial({
+          authority: kp.publicKey,
+          market: marketPda,
+        })
+        .signers([kp])
+        .rpc();
+
+      await program.methods
+        .tradeShares({
+          shares: new BN(10),
+          isBuy: true,
+          isPass,
+        })
+        .accountsPartial({
+          authority: kp.publicKey,
+          configAuthority: configAuthority.publicKey,
+          market: marketPda,
+          position: getPositionPda(kp.publicKey, marketPda),
+          tradingMint: USDC_MINT,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([kp])
+        .rpc();
+    }
+
+    forwardTime(litesvm, marketOpenPeriod + 60); // forward time by marketOpenPeriod + 1 minute
+
+    await program.methods
+      .settleMarket()
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        market: marketPda,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const marketAcc = await fetchMarketAcc(program, marketPda);
+
+    expect(marketAcc.state).toEqual({ resolved: {} });
+
+    const marketAta = getAssociatedTokenAddressSync(
+      USDC_MINT,
+      marketPda,
+      true,
+      TOKEN_PROGRAM_ID
+    );
+    const preMarketAtaAcc = await getAccount(
+      provider.connection,
+      marketAta,
+      "processed"
+    );
+
+    await program.methods
+      .claimWinnings()
+      .accountsPartial({
+        authority: longPositionAuthority.publicKey,
+        market: marketPda,
+        position: getPositionPda(longPositionAuthority.publicKey, marketPda),
+        tradingMint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([longPositionAuthority])
+      .rpc();
+
+    const postMarketAtaAcc = await getAccount(
+      provider.connection,
+      marketAta,
+      "processed"
+    );
+
+    const fraction = Math.min(
+      Math.max((marketAcc.settledPrice - lower) / (upper - lower), 0),
+      1
+    );
+    const longBps = Math.floor(fraction * 10_000);
+
+    expect(
+      Number(preMarketAtaAcc.amount) - Number(postMarketAtaAcc.amount)
+    ).toBe(Math.floor((Number(preMarketAtaAcc.amount) * longBps) / 10_000));
+  });
+});
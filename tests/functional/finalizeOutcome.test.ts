@@ -0,0 +1,241 @@
+import { BN, Program } from "@coral-xyz/anchor";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { beforeEach, describe, expect, test } from "bun:test";
+import { LiteSVM } from "litesvm";
+import { Prediction } from "../../target/types/prediction";
+import { Keypair } from "@solana/web3.js";
+import {
+  expectAnchorError,
+  forwardTime,
+  fundedSystemAccountInfo,
+  getSetup,
+  initUsdcAta,
+} from "../setup";
+import { getMarketPda, getResolutionPda } from "../pda";
+import { fetchMarketAcc, fetchResolutionAcc } from "../accounts";
+import {
+  DISPUTE_WINDOW,
+  MAX_MARKET_DURATION,
+  MIN_MARKET_DURATION,
+  PROPOSAL_BOND,
+  USDC_MINT,
+  USDC_MINT_DECIMALS,
+} from "../constants";
+import {
+  getAccount,
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+
+describe("finalizeOutcome", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<Prediction>;
+  };
+
+  const [configAuthority, proposer, challenger] = Array.from(
+    { length: 3 },
+    () => Keypair.generate()
+  );
+
+  const initAtaBal = 100 * 10 ** USDC_MINT_DECIMALS;
+  const marketOpenPeriod = 60 * 60 * 24; // 1 day
+  const marketPda = getMarketPda(0);
+  const resolutionPda = getResolutionPda(marketPda);
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      ...[configAuthority, proposer, challenger].map((kp) => {
+        return {
+          pubkey: kp.publicKey,
+          account: fundedSystemAccountInfo(),
+        };
+      }),
+    ]));
+
+    for (const kp of [proposer, challenger]) {
+      initUsdcAta(litesvm, kp.publicKey, initAtaBal);
+    }
+
+    await program.methods
+      .initializeConfig({
+        feeBps: 10,
+        minMarketDuration: new BN(MIN_MARKET_DURATION),
+        maxMarketDuration: new BN(MAX_MARKET_DURATION),
+        proposalBond: new BN(PROPOSAL_BOND),
+        disputeWindow: new BN(DISPUTE_WINDOW),
+      })
+      .accounts({
+        authority: configAuthority.publicKey,
+        mint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const now = litesvm.getClock().unixTimestamp;
+
+    await program.methods
+      .createManualMarket({
+        resolveTs: new BN(Number(now) + marketOpenPeriod),
+        resolver: null,
+        title: "Will the incumbent win the election?",
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tradingMint: USDC_MINT,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    forwardTime(litesvm, marketOpenPeriod);
+
+    await program.methods
+      .proposeOutcome({ outcome: 0, settledPrice: null })
+      .accountsPartial({
+        proposer: proposer.publicKey,
+        market: marketPda,
+        tradingMint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([proposer])
+      .rpc();
+  });
+
+  async function getBalance(owner: Keypair) {
+    const ata = getAssociatedTokenAddressSync(
+      USDC_MINT,
+      owner.publicKey,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+
+    const ataAcc = await getAccount(provider.connection, ata, "processed");
+
+    return Number(ataAcc.amount);
+  }
+
+  test("finalizes an undisputed proposal after the dispute window", async () => {
+    forwardTime(litesvm, DISPUTE_WINDOW);
+
+    await program.methods
+      .finalizeOutcome({ outcome: null, settledPrice: null })
+      .accountsPartial({
+        authority: challenger.publicKey,
+        market: marketPda,
+        tradingMint: USDC_MINT,
+        winnerTokenAccount: getAssociatedTokenAddressSync(
+          USDC_MINT,
+          proposer.publicKey,
+          false,
+          TOKEN_PROGRAM_ID
+        ),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([challenger])
+      .rpc();
+
+    const marketAcc = await fetchMarketAcc(program, marketPda);
+    const resolutionAcc = await fetchResolutionAcc(program, resolutionPda);
+
+    expect(marketAcc.state).toEqual({ passed: {} });
+    expect(resolutionAcc.state).toEqual({ finalized: {} });
+    expect(resolutionAcc.finalOutcome).toBe(0);
+    expect(await getBalance(proposer)).toBe(initAtaBal);
+  });
+
+  test("slashes the proposer's bond when the dispute is upheld", async () => {
+    await program.methods
+      .disputeOutcome()
+      .accountsPartial({
+        challenger: challenger.publicKey,
+        resolution: resolutionPda,
+        tradingMint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([challenger])
+      .rpc();
+
+    await program.methods
+      .finalizeOutcome({ outcome: 1, settledPrice: null })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        market: marketPda,
+        tradingMint: USDC_MINT,
+        winnerTokenAccount: getAssociatedTokenAddressSync(
+          USDC_MINT,
+          challenger.publicKey,
+          false,
+          TOKEN_PROGRAM_ID
+        ),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const marketAcc = await fetchMarketAcc(program, marketPda);
+
+    expect(marketAcc.state).toEqual({ failed: {} });
+    expect(await getBalance(challenger)).toBe(initAtaBal + PROPOSAL_BOND);
+    expect(await getBalance(proposer)).toBe(initAtaBal - PROPOSAL_BOND);
+  });
+
+  test("throws if dispute window is still open", async () => {
+    try {
+      await program.methods
+        .finalizeOutcome({ outcome: null, settledPrice: null })
+        .accountsPartial({
+          authority: challenger.publicKey,
+          market: marketPda,
+          tradingMint: USDC_MINT,
+          winnerTokenAccount: getAssociatedTokenAddressSync(
+            USDC_MINT,
+            proposer.publicKey,
+            false,
+            TOKEN_PROGRAM_ID
+          ),
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([challenger])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "DisputeWindowOpen");
+    }
+  });
+
+  test("throws if disputed proposal is finalized by a non-authority", async () => {
+    await program.methods
+      .disputeOutcome()
+      .accountsPartial({
+        challenger: challenger.publicKey,
+        resolution: resolutionPda,
+        tradingMint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([challenger])
+      .rpc();
+
+    try {
+      await program.methods
+        .finalizeOutcome({ outcome: 1, settledPrice: null })
+        .accountsPartial({
+          authority: challenger.publicKey,
+          market: marketPda,
+          tradingMint: USDC_MINT,
+          winnerTokenAccount: getAssociatedTokenAddressSync(
+            USDC_MINT,
+            challenger.publicKey,
+            false,
+            TOKEN_PROGRAM_ID
+          ),
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([challenger])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidConfigAuthority");
+    }
+  });
+});
@@ -1,15 +1,29 @@
-import { Program } from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
 import { LiteSVMProvider } from "anchor-litesvm";
 import { beforeEach, describe, expect, test } from "bun:test";
 import { LiteSVM } from "litesvm";
 import { Prediction } from "../../target/types/prediction";
-import { Keypair } from "@solana/web3.js";
-import { fundedSystemAccountInfo, getSetup } from "../setup";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  expectAnchorError,
+  fundedSystemAccountInfo,
+  getSetup,
+  initToken2022Mint,
+} from "../setup";
 import { getConfigPda } from "../pda";
 import { fetchConfigAcc } from "../accounts";
-import { USDC_MINT } from "../constants";
 import {
+  DISPUTE_WINDOW,
+  MAX_MARKET_DURATION,
+  MIN_MARKET_DURATION,
+  PROPOSAL_BOND,
+  USDC_MINT,
+  USDC_MINT_DECIMALS,
+} from "../constants";
+import {
+  ExtensionType,
   getAssociatedTokenAddressSync,
+  TOKEN_2022_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 
@@ -37,6 +51,10 @@ describe("initializeConfig", () => {
     await program.methods
       .initializeConfig({
         feeBps,
+        minMarketDuration: new BN(MIN_MARKET_DURATION),
+        maxMarketDuration: new BN(MAX_MARKET_DURATION),
+        proposalBond: new BN(PROPOSAL_BOND),
+        disputeWindow: new BN(DISPUTE_WINDOW),
       })
       .accounts({
         authority: configAuthority.publicKey,
@@ -52,6 +70,9 @@ describe("initializeConfig", () => {
     expect(configAcc.authority).toStrictEqual(configAuthority.publicKey);
     expect(configAcc.feeBps).toBe(feeBps);
     expect(configAcc.mint).toStrictEqual(USDC_MINT);
+    expect(configAcc.sharePrice.toNumber()).toBe(10 ** USDC_MINT_DECIMALS);
+    expect(configAcc.proposalBond.toNumber()).toBe(PROPOSAL_BOND);
+    expect(configAcc.disputeWindow.toNumber()).toBe(DISPUTE_WINDOW);
 
     const configAta = getAssociatedTokenAddressSync(
       USDC_MINT,
@@ -63,4 +84,60 @@ describe("initializeConfig", () => {
 
     expect(configAtaAcc).not.toBeNull();
   });
+
+  test("throws if mint has an unsupported extension", async () => {
+    const mint = PublicKey.unique();
+
+    initToken2022Mint(litesvm, mint, USDC_MINT_DECIMALS, [
+      ExtensionType.NonTransferable,
+    ]);
+
+    try {
+      await program.methods
+        .initializeConfig({
+          feeBps: 10,
+          minMarketDuration: new BN(MIN_MARKET_DURATION),
+          maxMarketDuration: new BN(MAX_MARKET_DURATION),
+          proposalBond: new BN(PROPOSAL_BOND),
+          disputeWindow: new BN(DISPUTE_WINDOW),
+        })
+        .accounts({
+          authority: configAuthority.publicKey,
+          mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([configAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "UnsupportedMintExtension");
+    }
+  });
+
+  test("throws if mint has a permanent delegate", async () => {
+    const mint = PublicKey.unique();
+
+    initToken2022Mint(litesvm, mint, USDC_MINT_DECIMALS, [
+      ExtensionType.PermanentDelegate,
+    ]);
+
+    try {
+      await program.methods
+        .initializeConfig({
+          feeBps: 10,
+          minMarketDuration: new BN(MIN_MARKET_DURATION),
+          maxMarketDuration: new BN(MAX_MARKET_DURATION),
+          proposalBond: new BN(PROPOSAL_BOND),
+          disputeWindow: new BN(DISPUTE_WINDOW),
+        })
+        .accounts({
+          authority: configAuthority.publicKey,
+          mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([configAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "UnsupportedMintExtension");
+    }
+  });
 });
@@ -0,0 +1,150 @@
+import { Program } from "@coral-xyz/anchor";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { beforeEach, describe, expect, test } from "bun:test";
+import { LiteSVM } from "litesvm";
+import { Prediction } from "../../target/types/prediction";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+import { getConfigPda } from "../pda";
+import { fetchConfigAcc } from "../accounts";
+import {
+  PREDICTION_PROGRAM_ID,
+  USDC_MINT,
+  USDC_MINT_DECIMALS,
+} from "../constants";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+describe("migrateConfig", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<Prediction>;
+  };
+
+  const configAuthority = Keypair.generate();
+  const feeBps = 10;
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      {
+        pubkey: configAuthority.publicKey,
+        account: fundedSystemAccountInfo(),
+      },
+    ]));
+
+    const [configPda, bump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      PREDICTION_PROGRAM_ID
+    );
+    const discriminator = program.idl.accounts.find(
+      (acc) => acc.name === "config"
+    ).discriminator;
+
+    // unversioned layout: authority, fee_bps, mint, bump
+    const legacyData = Buffer.alloc(8 + 32 + 2 + 32 + 1);
+    Buffer.from(discriminator).copy(legacyData, 0);
+    configAuthority.publicKey.toBuffer().copy(legacyData, 8);
+    legacyData.writeUInt16LE(feeBps, 40);
+    USDC_MINT.toBuffer().copy(legacyData, 42);
+    legacyData.writeUInt8(bump, 74);
+
+    litesvm.setAccount(configPda, {
+      data: legacyData,
+      executable: false,
+      lamports: LAMPORTS_PER_SOL,
+      owner: PREDICTION_PROGRAM_ID,
+    });
+  });
+
+  test("migrates a legacy config", async () => {
+    await program.methods
+      .migrateConfig()
+      .accounts({
+        authority: configAuthority.publicKey,
+        mint: USDC_MINT,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const configAcc = await fetchConfigAcc(program, getConfigPda());
+
+    expect(configAcc.version).toBe(2);
+    expect(configAcc.authority).toStrictEqual(configAuthority.publicKey);
+    expect(configAcc.feeBps).toBe(feeBps);
+    expect(configAcc.mint).toStrictEqual(USDC_MINT);
+    expect(configAcc.sharePrice.toNumber()).toBe(10 ** USDC_MINT_DECIMALS);
+    expect(configAcc.proposalBond.toNumber()).toBe(10 ** USDC_MINT_DECIMALS);
+    expect(configAcc.disputeWindow.toNumber()).toBe(60 * 60 * 2);
+  });
+
+  test("migrates a v1 config", async () => {
+    const [configPda, bump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      PREDICTION_PROGRAM_ID
+    );
+    const discriminator = program.idl.accounts.find(
+      (acc) => acc.name === "config"
+    ).discriminator;
+
+    // v1 layout: version, authority, fee_bps, mint, share_price, bump, reserved
+    const v1Data = Buffer.alloc(8 + 1 + 32 + 2 + 32 + 8 + 1 + 128);
+    Buffer.from(discriminator).copy(v1Data, 0);
+    v1Data.writeUInt8(1, 8);
+    configAuthority.publicKey.toBuffer().copy(v1Data, 9);
+    v1Data.writeUInt16LE(feeBps, 41);
+    USDC_MINT.toBuffer().copy(v1Data, 43);
+    v1Data.writeBigUInt64LE(BigInt(10 ** USDC_MINT_DECIMALS), 75);
+    v1Data.writeUInt8(bump, 83);
+
+    litesvm.setAccount(configPda, {
+      data: v1Data,
+      executable: false,
+      lamports: LAMPORTS_PER_SOL,
+      owner: PREDICTION_PROGRAM_ID,
+    });
+
+    await program.methods
+      .migrateConfig()
+      .accounts({
+        authority: configAuthority.publicKey,
+        mint: USDC_MINT,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const configAcc = await fetchConfigAcc(program, getConfigPda());
+
+    expect(configAcc.version).toBe(2);
+    expect(configAcc.authority).toStrictEqual(configAuthority.publicKey);
+    expect(configAcc.feeBps).toBe(feeBps);
+    expect(configAcc.bump).toBe(bump);
+    expect(configAcc.minMarketDuration.toNumber()).toBe(5 * 60);
+    expect(configAcc.maxMarketDuration.toNumber()).toBe(365 * 24 * 60 * 60);
+    expect(configAcc.proposalBond.toNumber()).toBe(10 ** USDC_MINT_DECIMALS);
+    expect(configAcc.disputeWindow.toNumber()).toBe(60 * 60 * 2);
+  });
+
+  test("throws if config is already migrated", async () => {
+    await program.methods
+      .migrateConfig()
+      .accounts({
+        authority: configAuthority.publicKey,
+        mint: USDC_MINT,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    try {
+      await program.methods
+        .migrateConfig()
+        .accounts({
+          authority: configAuthority.publicKey,
+          mint: USDC_MINT,
+        })
+        .signers([configAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "AccountAlreadyMigrated");
+    }
+  });
+});
@@ -0,0 +1,78 @@
+import { Program } from "@coral-xyz/anchor";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { beforeEach, describe, expect, test } from "bun:test";
+import { LiteSVM } from "litesvm";
+import { Prediction } from "../../target/types/prediction";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { fundedSystemAccountInfo, getSetup } from "../setup";
+import { fetchPositionAcc } from "../accounts";
+import { PREDICTION_PROGRAM_ID } from "../constants";
+
+describe("migratePosition", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<Prediction>;
+  };
+
+  const positionAuthority = Keypair.generate();
+  const marketPda = PublicKey.unique();
+  const [positionPda, bump] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("position"),
+      positionAuthority.publicKey.toBuffer(),
+      marketPda.toBuffer(),
+    ],
+    PREDICTION_PROGRAM_ID
+  );
+  const passShares = 10;
+  const failShares = 5;
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      {
+        pubkey: positionAuthority.publicKey,
+        account: fundedSystemAccountInfo(),
+      },
+    ]));
+
+    const discriminator = program.idl.accounts.find(
+      (acc) => acc.name === "position"
+    ).discriminator;
+
+    // unversioned layout: authority, market, pass_shares, fail_shares, bump
+    const legacyData = Buffer.alloc(8 + 32 + 32 + 8 + 8 + 1);
+    Buffer.from(discriminator).copy(legacyData, 0);
+    positionAuthority.publicKey.toBuffer().copy(legacyData, 8);
+    marketPda.toBuffer().copy(legacyData, 40);
+    legacyData.writeBigUInt64LE(BigInt(passShares), 72);
+    legacyData.writeBigUInt64LE(BigInt(failShares), 80);
+    legacyData.writeUInt8(bump, 88);
+
+    litesvm.setAccount(positionPda, {
+      data: legacyData,
+      executable: false,
+      lamports: LAMPORTS_PER_SOL,
+      owner: PREDICTION_PROGRAM_ID,
+    });
+  });
+
+  test("migrates a legacy position", async () => {
+    await program.methods
+      .migratePosition()
+      .accounts({
+        payer: positionAuthority.publicKey,
+        position: positionPda,
+      })
+      .signers([positionAuthority])
+      .rpc();
+
+    const positionAcc = await fetchPositionAcc(program, positionPda);
+
+    expect(positionAcc.version).toBe(1);
+    expect(positionAcc.authority).toStrictEqual(positionAuthority.publicKey);
+    expect(positionAcc.market).toStrictEqual(marketPda);
+    expect(positionAcc.passShares.toNumber()).toBe(passShares);
+    expect(positionAcc.failShares.toNumber()).toBe(failShares);
+  });
+});
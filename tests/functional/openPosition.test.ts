@@ -6,8 +6,15 @@ import { Prediction } from "../../target/types/prediction";
 import { Keypair } from "@solana/web3.js";
 import { fundedSystemAccountInfo, getSetup } from "../setup";
 import { getMarketPda, getPositionPda } from "../pda";
-import { fetchPositionAcc } from "../accounts";
-import { SOL_USD_PRICE_UPDATE_V2, USDC_MINT } from "../constants";
+import { fetchMarketAcc, fetchPositionAcc } from "../accounts";
+import {
+  DISPUTE_WINDOW,
+  MAX_MARKET_DURATION,
+  MIN_MARKET_DURATION,
+  PROPOSAL_BOND,
+  SOL_USD_PRICE_UPDATE_V2,
+  USDC_MINT,
+} from "../constants";
 import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
 
 describe("openPosition", () => {
@@ -36,6 +43,10 @@ describe("openPosition", () => {
     await program.methods
       .initializeConfig({
         feeBps: 10,
+        minMarketDuration: new BN(MIN_MARKET_DURATION),
+        maxMarketDuration: new BN(MAX_MARKET_DURATION),
+        proposalBond: new BN(PROPOSAL_BOND),
+        disputeWindow: new BN(DISPUTE_WINDOW),
       })
       .accounts({
         authority: configAuthority.publicKey,
@@ -65,10 +76,7 @@ describe("openPosition", () => {
   });
 
   test("open a position", async () => {
-    const now = litesvm.getClock().unixTimestamp;
-    const resolveTs = Number(now) + marketOpenPeriod;
-
-    const marketPda = getMarketPda(SOL_USD_PRICE_UPDATE_V2, resolveTs);
+    const marketPda = getMarketPda(0);
 
     await program.methods
       .openPosition()
@@ -84,5 +92,9 @@ describe("openPosition", () => {
 
     expect(positionAcc.authority).toStrictEqual(positionAuthority.publicKey);
     expect(positionAcc.market).toStrictEqual(marketPda);
+
+    const marketAcc = await fetchMarketAcc(program, marketPda);
+
+    expect(marketAcc.uniqueTraders.toNumber()).toBe(1);
   });
 });
@@ -0,0 +1,172 @@
+import { BN, Program } from "@coral-xyz/anchor";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { beforeEach, describe, expect, test } from "bun:test";
+import { LiteSVM } from "litesvm";
+import { Prediction } from "../../target/types/prediction";
+import { Keypair } from "@solana/web3.js";
+import {
+  expectAnchorError,
+  forwardTime,
+  fundedSystemAccountInfo,
+  getSetup,
+  initUsdcAta,
+} from "../setup";
+import { getMarketPda, getResolutionPda } from "../pda";
+import { fetchMarketAcc, fetchResolutionAcc } from "../accounts";
+import {
+  DISPUTE_WINDOW,
+  MAX_MARKET_DURATION,
+  MIN_MARKET_DURATION,
+  PROPOSAL_BOND,
+  USDC_MINT,
+  USDC_MINT_DECIMALS,
+} from "../constants";
+import {
+  getAccount,
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+
+describe("proposeOutcome", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<Prediction>;
+  };
+
+  const [configAuthority, proposer, challenger] = Array.from(
+    { length: 3 },
+    () => Keypair.generate()
+  );
+
+  const initAtaBal = 100 * 10 ** USDC_MINT_DECIMALS;
+  const marketOpenPeriod = 60 * 60 * 24; // 1 day
+  const marketPda = getMarketPda(0);
+  const resolutionPda = getResolutionPda(marketPda);
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      ...[configAuthority, proposer, challenger].map((kp) => {
+        return {
+          pubkey: kp.publicKey,
+          account: fundedSystemAccountInfo(),
+        };
+      }),
+    ]));
+
+    for (const kp of [proposer, challenger]) {
+      initUsdcAta(litesvm, kp.publicKey, initAtaBal);
+    }
+
+    await program.methods
+      .initializeConfig({
+        feeBps: 10,
+        minMarketDuration: new BN(MIN_MARKET_DURATION),
+        maxMarketDuration: new BN(MAX_MARKET_DURATION),
+        proposalBond: new BN(PROPOSAL_BOND),
+        disputeWindow: new BN(DISPUTE_WINDOW),
+      })
+      .accounts({
+        authority: configAuthority.publicKey,
+        mint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const now = litesvm.getClock().unixTimestamp;
+
+    await program.methods
+      .createManualMarket({
+        resolveTs: new BN(Number(now) + marketOpenPeriod),
+        resolver: null,
+        title: "Will the incumbent win the election?",
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tradingMint: USDC_MINT,
+      })
+      .signers([configAuthority])
+      .rpc();
+  });
+
+  async function getBalance(owner: Keypair) {
+    const ata = getAssociatedTokenAddressSync(
+      USDC_MINT,
+      owner.publicKey,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+
+    const ataAcc = await getAccount(provider.connection, ata, "processed");
+
+    return Number(ataAcc.amount);
+  }
+
+  test("proposes an outcome with a bond", async () => {
+    forwardTime(litesvm, marketOpenPeriod);
+
+    await program.methods
+      .proposeOutcome({ outcome: 0, settledPrice: null })
+      .accountsPartial({
+        proposer: proposer.publicKey,
+        market: marketPda,
+        tradingMint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([proposer])
+      .rpc();
+
+    const resolutionAcc = await fetchResolutionAcc(program, resolutionPda);
+    const now = Number(litesvm.getClock().unixTimestamp);
+
+    expect(resolutionAcc.market).toStrictEqual(marketPda);
+    expect(resolutionAcc.state).toEqual({ proposed: {} });
+    expect(resolutionAcc.proposer).toStrictEqual(proposer.publicKey);
+    expect(resolutionAcc.proposedOutcome).toBe(0);
+    expect(resolutionAcc.disputeDeadline.toNumber()).toBe(now + DISPUTE_WINDOW);
+    expect(resolutionAcc.bond.toNumber()).toBe(PROPOSAL_BOND);
+    expect(await getBalance(proposer)).toBe(initAtaBal - PROPOSAL_BOND);
+
+    const marketAcc = await fetchMarketAcc(program, marketPda);
+
+    expect(marketAcc.state).toEqual({ initialized: {} });
+  });
+
+  test("throws if market cannot be resolved yet", async () => {
+    try {
+      await program.methods
+        .proposeOutcome({ outcome: 0, settledPrice: null })
+        .accountsPartial({
+          proposer: proposer.publicKey,
+          market: marketPda,
+          tradingMint: USDC_MINT,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([proposer])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "MarketCannotResolve");
+    }
+  });
+
+  test("throws if outcome is not PASS or FAIL", async () => {
+    forwardTime(litesvm, marketOpenPeriod);
+
+    try {
+      await program.methods
+        .proposeOutcome({ outcome: 2, settledPrice: null })
+        .accountsPartial({
+          proposer: proposer.publicKey,
+          market: marketPda,
+          tradingMint: USDC_MINT,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([proposer])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidOutcome");
+    }
+  });
+});
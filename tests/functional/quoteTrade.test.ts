@@ -0,0 +1,205 @@
+import { BN, Program } from "@coral-xyz/anchor";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { beforeEach, describe, expect, test } from "bun:test";
+import { LiteSVM } from "litesvm";
+import { Prediction } from "../../target/types/prediction";
+import { Keypair } from "@solana/web3.js";
+import {
+  expectAnchorError,
+  fundedSystemAccountInfo,
+  getSetup,
+  initUsdcAta,
+} from "../setup";
+import { getMarketPda, getPositionPda } from "../pda";
+import {
+  DISPUTE_WINDOW,
+  MAX_MARKET_DURATION,
+  MIN_MARKET_DURATION,
+  PROPOSAL_BOND,
+  SOL_USD_PRICE_UPDATE_V2,
+  USDC_MINT,
+  USDC_MINT_DECIMALS,
+} from "../constants";
+import {
+  getAccount,
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+
+describe("quoteTrade", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<Prediction>;
+  };
+
+  const [configAuthority, positionAuthority] = Array.from({ length: 2 }, () =>
+    Keypair.generate()
+  );
+
+  const initAtaBal = 100 * 10 ** USDC_MINT_DECIMALS;
+  const marketOpenPeriod = 60 * 60 * 24; // 1 day
+  const marketPda = getMarketPda(0);
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      ...[configAuthority, positionAuthority].map((kp) => {
+        return {
+          pubkey: kp.publicKey,
+          account: fundedSystemAccountInfo(),
+        };
+      }),
+    ]));
+
+    initUsdcAta(litesvm, positionAuthority.publicKey, initAtaBal);
+
+    await program.methods
+      .initializeConfig({
+        feeBps: 10,
+        minMarketDuration: new BN(MIN_MARKET_DURATION),
+        maxMarketDuration: new BN(MAX_MARKET_DURATION),
+        proposalBond: new BN(PROPOSAL_BOND),
+        disputeWindow: new BN(DISPUTE_WINDOW),
+      })
+      .accounts({
+        authority: configAuthority.publicKey,
+        mint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const now = litesvm.getClock().unixTimestamp;
+
+    await program.methods
+      .createMarket({
+        resolveTs: new BN(Number(now) + marketOpenPeriod),
+        targetPrice: 150,
+        title: "Will SOL reach $150 in 24 hours?",
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tradingMint: USDC_MINT,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    await program.methods
+      .openPosition()
+      .accountsPartial({
+        authority: positionAuthority.publicKey,
+        market: marketPda,
+      })
+      .signers([positionAuthority])
+      .rpc();
+  });
+
+  async function tradeShares(shares: number, isPass: boolean, isBuy: boolean) {
+    await program.methods
+      .tradeShares({
+        shares: new BN(shares),
+        isBuy,
+        isPass,
+      })
+      .accountsPartial({
+        authority: positionAuthority.publicKey,
+        configAuthority: configAuthority.publicKey,
+        market: marketPda,
+        position: getPositionPda(positionAuthority.publicKey, marketPda),
+        tradingMint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([positionAuthority])
+      .rpc();
+  }
+
+  async function getBalance() {
+    const ata = getAssociatedTokenAddressSync(
+      USDC_MINT,
+      positionAuthority.publicKey,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+
+    return Number((await getAccount(provider.connection, ata)).amount);
+  }
+
+  test("quote matches the executed buy", async () => {
+    await tradeShares(10, true, true);
+
+    const quote = await program.methods
+      .quoteTrade({
+        shares: new BN(5),
+        isBuy: true,
+        isPass: false,
+      })
+      .accountsPartial({
+        market: marketPda,
+        tradingMint: USDC_MINT,
+      })
+      .view();
+
+    const preBal = await getBalance();
+
+    await tradeShares(5, false, true);
+
+    const postBal = await getBalance();
+
+    expect(preBal - postBal).toBe(
+      quote.fee.toNumber() + quote.amount.toNumber()
+    );
+
+    // the next FAIL share is priced by its share of all outcome shares once bought
+    expect(quote.newFailPrice.toNumber()).toBe(
+      Math.floor((2 * 6 * 10 ** USDC_MINT_DECIMALS) / 16)
+    );
+    expect(quote.newPassPrice.toNumber()).toBeGreaterThan(
+      quote.newFailPrice.toNumber()
+    );
+  });
+
+  test("quote matches the executed sale", async () => {
+    await tradeShares(10, true, true);
+    await tradeShares(2, false, true);
+
+    const quote = await program.methods
+      .quoteTrade({
+        shares: new BN(4),
+        isBuy: false,
+        isPass: true,
+      })
+      .accountsPartial({
+        market: marketPda,
+        tradingMint: USDC_MINT,
+      })
+      .view();
+
+    const preBal = await getBalance();
+
+    await tradeShares(4, true, false);
+
+    const postBal = await getBalance();
+
+    expect(postBal - preBal).toBe(quote.amount.toNumber());
+  });
+
+  test("throws if selling more shares than the market holds", async () => {
+    try {
+      await program.methods
+        .quoteTrade({
+          shares: new BN(1),
+          isBuy: false,
+          isPass: true,
+        })
+        .accountsPartial({
+          market: marketPda,
+          tradingMint: USDC_MINT,
+        })
+        .view();
+    } catch (err) {
+      expectAnchorError(err, "InsufficientSharesToSell");
+    }
+  });
+});
@@ -0,0 +1,279 @@
+import { BN, Program } from "@coral-xyz/anchor";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { beforeEach, describe, expect, test } from "bun:test";
+import { LiteSVM } from "litesvm";
+import { Prediction } from "../../target/types/prediction";
+import { Keypair } from "@solana/web3.js";
+import {
+  expectAnchorError,
+  forwardTime,
+  fundedSystemAccountInfo,
+  getSetup,
+} from "../setup";
+import { getMarketPda, getPriceSamplesPda } from "../pda";
+import { fetchMarketAcc, fetchPriceSamplesAcc } from "../accounts";
+import {
+  DISPUTE_WINDOW,
+  MAX_MARKET_DURATION,
+  MIN_MARKET_DURATION,
+  PROPOSAL_BOND,
+  SOL_USD_PRICE_UPDATE_V2,
+  USDC_MINT,
+} from "../constants";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+describe("recordPriceSample", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<Prediction>;
+  };
+
+  const [configAuthority, nonAuthority] = Array.from({ length: 2 }, () =>
+    Keypair.generate()
+  );
+
+  const marketOpenPeriod = 60 * 60 * 24; // 1 day
+  const twapWindow = 32 * 60; // 32 minutes
+  const sampleInterval = twapWindow / 32;
+  const marketPda = getMarketPda(0);
+  const priceSamplesPda = getPriceSamplesPda(marketPda);
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      ...[configAuthority, nonAuthority].map((kp) => {
+        return {
+          pubkey: kp.publicKey,
+          account: fundedSystemAccountInfo(),
+        };
+      }),
+    ]));
+
+    await program.methods
+      .initializeConfig({
+        feeBps: 10,
+        minMarketDuration: new BN(MIN_MARKET_DURATION),
+        maxMarketDuration: new BN(MAX_MARKET_DURATION),
+        proposalBond: new BN(PROPOSAL_BOND),
+        disputeWindow: new BN(DISPUTE_WINDOW),
+      })
+      .accounts({
+        authority: configAuthority.publicKey,
+        mint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const now = litesvm.getClock().unixTimestamp;
+
+    await program.methods
+      .createMarket({
+        resolveTs: new BN(Number(now) + marketOpenPeriod),
+        targetPrice: 150,
+        title: "Will SOL reach $150 in 24 hours?",
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tradingMint: USDC_MINT,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    await program.methods
+      .setMarketTwap({ window: new BN(twapWindow) })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        market: marketPda,
+      })
+      .signers([configAuthority])
+      .rpc();
+  });
+
+  async function recordPriceSample() {
+    await program.methods
+      .recordPriceSample()
+      .accountsPartial({
+        market: marketPda,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+      })
+      .rpc();
+  }
+
+  test("set market TWAP", async () => {
+    const marketAcc = await fetchMarketAcc(program, marketPda);
+
+    expect(marketAcc.twapWindow.toNumber()).toBe(twapWindow);
+
+    const priceSamplesAcc = await fetchPriceSamplesAcc(
+      program,
+      priceSamplesPda
+    );
+
+    expect(priceSamplesAcc.market).toStrictEqual(marketPda);
+    expect(priceSamplesAcc.count).toBe(0);
+  });
+
+  test("record price samples and settle against TWAP", async () => {
+    forwardTime(litesvm, marketOpenPeriod - twapWindow);
+
+    await recordPriceSample();
+
+    forwardTime(litesvm, sampleInterval);
+
+    await recordPriceSample();
+
+    const priceSamplesAcc = await fetchPriceSamplesAcc(
+      program,
+      priceSamplesPda
+    );
+
+    expect(priceSamplesAcc.count).toBe(2);
+    expect(priceSamplesAcc.head).toBe(2);
+    expect(priceSamplesAcc.samples[0].price.toString()).toBe("19114841996");
+    expect(priceSamplesAcc.samples[0].exponent).toBe(-8);
+
+    forwardTime(litesvm, twapWindow);
+
+    await program.methods
+      .settleMarket()
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        market: marketPda,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        priceSamples: priceSamplesPda,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const marketAcc = await fetchMarketAcc(program, marketPda);
+
+    expect(marketAcc.state).toEqual({ passed: {} });
+    expect(marketAcc.settledPrice).toBeCloseTo(191.14841996);
+  });
+
+  test("settles as undecided without price samples", async () => {
+    forwardTime(litesvm, marketOpenPeriod + 60); // forward time by marketOpenPeriod + 1 minute
+
+    await program.methods
+      .settleMarket()
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        market: marketPda,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        priceSamples: priceSamplesPda,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const marketAcc = await fetchMarketAcc(program, marketPda);
+
+    expect(marketAcc.state).toEqual({ undecided: {} });
+  });
+
+  test("throws if settled without price samples account", async () => {
+    forwardTime(litesvm, marketOpenPeriod + 60); // forward time by marketOpenPeriod + 1 minute
+
+    try {
+      await program.methods
+        .settleMarket()
+        .accountsPartial({
+          authority: configAuthority.publicKey,
+          market: marketPda,
+          priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        })
+        .signers([configAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidPriceSamples");
+    }
+  });
+
+  test("throws if sampled before TWAP window", async () => {
+    try {
+      await recordPriceSample();
+    } catch (err) {
+      expectAnchorError(err, "OutsideSamplingWindow");
+    }
+  });
+
+  test("throws if sampled too soon after previous sample", async () => {
+    forwardTime(litesvm, marketOpenPeriod - twapWindow);
+
+    await recordPriceSample();
+
+    forwardTime(litesvm, sampleInterval - 1);
+
+    try {
+      await recordPriceSample();
+    } catch (err) {
+      expectAnchorError(err, "PriceSampleTooSoon");
+    }
+  });
+
+  test("throws if TWAP window exceeds time to resolve", async () => {
+    const now = litesvm.getClock().unixTimestamp;
+
+    await program.methods
+      .createMarket({
+        resolveTs: new BN(Number(now) + marketOpenPeriod),
+        targetPrice: 150,
+        title: "Will SOL reach $150 in 24 hours?",
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tradingMint: USDC_MINT,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    try {
+      await program.methods
+        .setMarketTwap({ window: new BN(marketOpenPeriod + 1) })
+        .accountsPartial({
+          authority: configAuthority.publicKey,
+          market: getMarketPda(1),
+        })
+        .signers([configAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidTwapWindow");
+    }
+  });
+
+  test("throws if authority is not config authority", async () => {
+    const now = litesvm.getClock().unixTimestamp;
+
+    await program.methods
+      .createMarket({
+        resolveTs: new BN(Number(now) + marketOpenPeriod),
+        targetPrice: 150,
+        title: "Will SOL reach $150 in 24 hours?",
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tradingMint: USDC_MINT,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    try {
+      await program.methods
+        .setMarketTwap({ window: new BN(twapWindow) })
+        .accountsPartial({
+          authority: nonAuthority.publicKey,
+          market: getMarketPda(1),
+        })
+        .signers([nonAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidConfigAuthority");
+    }
+  });
+});
@@ -0,0 +1,166 @@
+import { BN, Program } from "@coral-xyz/anchor";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { beforeEach, describe, expect, test } from "bun:test";
+import { LiteSVM } from "litesvm";
+import { Prediction } from "../../target/types/prediction";
+import { Keypair } from "@solana/web3.js";
+import {
+  expectAnchorError,
+  forwardTime,
+  fundedSystemAccountInfo,
+  getSetup,
+} from "../setup";
+import { getMarketPda } from "../pda";
+import { fetchMarketAcc } from "../accounts";
+import {
+  DISPUTE_WINDOW,
+  MAX_MARKET_DURATION,
+  MIN_MARKET_DURATION,
+  PROPOSAL_BOND,
+  SOL_USD_PRICE_UPDATE_V2,
+  USDC_MINT,
+} from "../constants";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+describe("resolveMarketManual", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<Prediction>;
+  };
+
+  const [configAuthority, resolver, nonResolver] = Array.from(
+    { length: 3 },
+    () => Keypair.generate()
+  );
+
+  const marketOpenPeriod = 60 * 60 * 24; // 1 day
+  const marketPda = getMarketPda(0);
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      ...[configAuthority, resolver, nonResolver].map((kp) => {
+        return {
+          pubkey: kp.publicKey,
+          account: fundedSystemAccountInfo(),
+        };
+      }),
+    ]));
+
+    await program.methods
+      .initializeConfig({
+        feeBps: 10,
+        minMarketDuration: new BN(MIN_MARKET_DURATION),
+        maxMarketDuration: new BN(MAX_MARKET_DURATION),
+        proposalBond: new BN(PROPOSAL_BOND),
+        disputeWindow: new BN(DISPUTE_WINDOW),
+      })
+      .accounts({
+        authority: configAuthority.publicKey,
+        mint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const now = litesvm.getClock().unixTimestamp;
+
+    await program.methods
+      .createManualMarket({
+        resolveTs: new BN(Number(now) + marketOpenPeriod),
+        resolver: resolver.publicKey,
+        title: "Will the incumbent win the election?",
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tradingMint: USDC_MINT,
+      })
+      .signers([configAuthority])
+      .rpc();
+  });
+
+  test("resolves a market to FAIL", async () => {
+    forwardTime(litesvm, marketOpenPeriod);
+
+    await program.methods
+      .resolveMarketManual({ outcome: 1 })
+      .accountsPartial({
+        resolver: resolver.publicKey,
+        market: marketPda,
+      })
+      .signers([resolver])
+      .rpc();
+
+    const marketAcc = await fetchMarketAcc(program, marketPda);
+
+    expect(marketAcc.state).toEqual({ failed: {} });
+  });
+
+  test("throws if signer is not the resolver", async () => {
+    forwardTime(litesvm, marketOpenPeriod);
+
+    try {
+      await program.methods
+        .resolveMarketManual({ outcome: 0 })
+        .accountsPartial({
+          resolver: nonResolver.publicKey,
+          market: marketPda,
+        })
+        .signers([nonResolver])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidResolver");
+    }
+  });
+
+  test("throws if market cannot be resolved yet", async () => {
+    try {
+      await program.methods
+        .resolveMarketManual({ outcome: 0 })
+        .accountsPartial({
+          resolver: resolver.publicKey,
+          market: marketPda,
+        })
+        .signers([resolver])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "MarketCannotResolve");
+    }
+  });
+
+  test("throws if outcome is not PASS or FAIL", async () => {
+    forwardTime(litesvm, marketOpenPeriod);
+
+    try {
+      await program.methods
+        .resolveMarketManual({ outcome: 2 })
+        .accountsPartial({
+          resolver: resolver.publicKey,
+          market: marketPda,
+        })
+        .signers([resolver])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidOutcome");
+    }
+  });
+
+  test("throws if manual market is settled through the oracle", async () => {
+    forwardTime(litesvm, marketOpenPeriod);
+
+    try {
+      await program.methods
+        .settleMarket()
+        .accountsPartial({
+          authority: configAuthority.publicKey,
+          market: marketPda,
+          priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        })
+        .signers([configAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidPriceUpdateV2");
+    }
+  });
+});
@@ -0,0 +1,213 @@
+import { BN, Program } from "@coral-xyz/anchor";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { beforeEach, describe, expect, test } from "bun:test";
+import { LiteSVM } from "litesvm";
+import { Prediction } from "../../target/types/prediction";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import {
+  expectAnchorError,
+  forwardTime,
+  fundedSystemAccountInfo,
+  getSetup,
+} from "../setup";
+import { getMarketPda } from "../pda";
+import { fetchMarketAcc } from "../accounts";
+import {
+  DISPUTE_WINDOW,
+  MAX_MARKET_DURATION,
+  MIN_MARKET_DURATION,
+  PROPOSAL_BOND,
+  SOL_USD_PRICE_UPDATE_V2,
+  SOL_USD_SWITCHBOARD_PULL_FEED,
+  SWITCHBOARD_ON_DEMAND_PROGRAM_ID,
+  USDC_MINT,
+} from "../constants";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import pullFeedAccInfo from "../fixtures/sol_usd_switchboard_pull_feed.json";
+
+describe("setMarketOracles", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<Prediction>;
+  };
+
+  const [configAuthority, nonAuthority] = Array.from({ length: 2 }, () =>
+    Keypair.generate()
+  );
+
+  const marketOpenPeriod = 60 * 60 * 24; // 1 day
+  const marketPda = getMarketPda(0);
+
+  // pull feed reporting a price far from the Pyth fixture
+  const divergentPullFeed = PublicKey.unique();
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      ...[configAuthority, nonAuthority].map((kp) => {
+        return {
+          pubkey: kp.publicKey,
+          account: fundedSystemAccountInfo(),
+        };
+      }),
+    ]));
+
+    const pullFeedData = Buffer.from(pullFeedAccInfo.account.data[0], "base64");
+    const resultValueOffset = 8 + 2256;
+    pullFeedData.writeBigInt64LE(100n * 10n ** 18n, resultValueOffset);
+    pullFeedData.writeBigInt64LE(0n, resultValueOffset + 8);
+
+    litesvm.setAccount(divergentPullFeed, {
+      data: pullFeedData,
+      executable: false,
+      lamports: LAMPORTS_PER_SOL,
+      owner: SWITCHBOARD_ON_DEMAND_PROGRAM_ID,
+    });
+
+    await program.methods
+      .initializeConfig({
+        feeBps: 10,
+        minMarketDuration: new BN(MIN_MARKET_DURATION),
+        maxMarketDuration: new BN(MAX_MARKET_DURATION),
+        proposalBond: new BN(PROPOSAL_BOND),
+        disputeWindow: new BN(DISPUTE_WINDOW),
+      })
+      .accounts({
+        authority: configAuthority.publicKey,
+        mint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const now = litesvm.getClock().unixTimestamp;
+
+    await program.methods
+      .createMarket({
+        resolveTs: new BN(Number(now) + marketOpenPeriod),
+        targetPrice: 150,
+        title: "Will SOL reach $150 in 24 hours?",
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tradingMint: USDC_MINT,
+      })
+      .signers([configAuthority])
+      .rpc();
+  });
+
+  async function setMarketOracles(oracle: PublicKey, toleranceBps: number) {
+    await program.methods
+      .setMarketOracles({ toleranceBps })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        market: marketPda,
+      })
+      .remainingAccounts([
+        { pubkey: oracle, isSigner: false, isWritable: false },
+      ])
+      .signers([configAuthority])
+      .rpc();
+  }
+
+  async function settleMarket(oracle: PublicKey) {
+    await program.methods
+      .settleMarket()
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        market: marketPda,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+      })
+      .remainingAccounts([
+        { pubkey: oracle, isSigner: false, isWritable: false },
+      ])
+      .signers([configAuthority])
+      .rpc();
+  }
+
+  test("settles against the median of multiple oracles", async () => {
+    await setMarketOracles(SOL_USD_SWITCHBOARD_PULL_FEED, 100);
+
+    const marketAcc = await fetchMarketAcc(program, marketPda);
+
+    expect(marketAcc.extraOracleCount).toBe(1);
+    expect(marketAcc.extraOracles[0]).toStrictEqual(
+      SOL_USD_SWITCHBOARD_PULL_FEED
+    );
+    expect(marketAcc.extraOracleSources[0]).toEqual({ switchboard: {} });
+    expect(marketAcc.oracleToleranceBps).toBe(100);
+
+    forwardTime(litesvm, marketOpenPeriod + 60); // forward time by marketOpenPeriod + 1 minute
+
+    await settleMarket(SOL_USD_SWITCHBOARD_PULL_FEED);
+
+    const settledMarketAcc = await fetchMarketAcc(program, marketPda);
+
+    expect(settledMarketAcc.state).toEqual({ passed: {} });
+    expect(settledMarketAcc.settledPrice).toBeCloseTo(191.14841996);
+  });
+
+  test("throws if oracle prices diverge beyond tolerance", async () => {
+    await setMarketOracles(divergentPullFeed, 100);
+
+    forwardTime(litesvm, marketOpenPeriod + 60); // forward time by marketOpenPeriod + 1 minute
+
+    try {
+      await settleMarket(divergentPullFeed);
+    } catch (err) {
+      expectAnchorError(err, "OraclePriceDivergence");
+    }
+  });
+
+  test("throws if settled without the additional oracles", async () => {
+    await setMarketOracles(SOL_USD_SWITCHBOARD_PULL_FEED, 100);
+
+    forwardTime(litesvm, marketOpenPeriod + 60); // forward time by marketOpenPeriod + 1 minute
+
+    try {
+      await program.methods
+        .settleMarket()
+        .accountsPartial({
+          authority: configAuthority.publicKey,
+          market: marketPda,
+          priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        })
+        .signers([configAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidPriceUpdateV2");
+    }
+  });
+
+  test("throws if primary oracle is repeated", async () => {
+    try {
+      await setMarketOracles(SOL_USD_PRICE_UPDATE_V2, 100);
+    } catch (err) {
+      expectAnchorError(err, "InvalidOracleCount");
+    }
+  });
+
+  test("throws if authority is not config authority", async () => {
+    try {
+      await program.methods
+        .setMarketOracles({ toleranceBps: 100 })
+        .accountsPartial({
+          authority: nonAuthority.publicKey,
+          market: marketPda,
+        })
+        .remainingAccounts([
+          {
+            pubkey: SOL_USD_SWITCHBOARD_PULL_FEED,
+            isSigner: false,
+            isWritable: false,
+          },
+        ])
+        .signers([nonAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidConfigAuthority");
+    }
+  });
+});
@@ -14,7 +14,12 @@ import {
 import { getConfigPda, getMarketPda, getPositionPda } from "../pda";
 import { fetchMarketAcc } from "../accounts";
 import {
+  DISPUTE_WINDOW,
+  MAX_MARKET_DURATION,
+  MIN_MARKET_DURATION,
+  PROPOSAL_BOND,
   SOL_USD_PRICE_UPDATE_V2,
+  SOL_USD_SWITCHBOARD_PULL_FEED,
   USDC_MINT,
   USDC_MINT_DECIMALS,
 } from "../constants";
@@ -50,6 +55,10 @@ describe("settleMarket", () => {
     await program.methods
       .initializeConfig({
         feeBps: 10,
+        minMarketDuration: new BN(MIN_MARKET_DURATION),
+        maxMarketDuration: new BN(MAX_MARKET_DURATION),
+        proposalBond: new BN(PROPOSAL_BOND),
+        disputeWindow: new BN(DISPUTE_WINDOW),
       })
       .accounts({
         authority: configAuthority.publicKey,
@@ -77,7 +86,7 @@ describe("settleMarket", () => {
       .signers([configAuthority])
       .rpc();
 
-    const marketPda = getMarketPda(SOL_USD_PRICE_UPDATE_V2, resolveTs);
+    const marketPda = getMarketPda(0);
 
     await program.methods
       .openPosition()
@@ -111,11 +120,44 @@ describe("settleMarket", () => {
   });
 
   test("settle a market", async () => {
+    const marketPda = getMarketPda(0);
+
+    forwardTime(litesvm, marketOpenPeriod + 60); // forward time by marketOpenPeriod + 1 minute
+
+    await program.methods
+      .settleMarket()
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        market: marketPda,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const marketAcc = await fetchMarketAcc(program, marketPda);
+
+    expect(marketAcc.state).toEqual({ passed: {} });
+  });
+
+  test("settle a market below target as failed", async () => {
     const now = litesvm.getClock().unixTimestamp;
-    const marketPda = getMarketPda(
-      SOL_USD_PRICE_UPDATE_V2,
-      Number(now) + marketOpenPeriod
-    );
+
+    await program.methods
+      .createMarket({
+        resolveTs: new BN(Number(now) + marketOpenPeriod),
+        targetPrice: 200,
+        title: "Will SOL reach $200 in 24 hours?",
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tradingMint: USDC_MINT,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const marketPda = getMarketPda(1);
 
     forwardTime(litesvm, marketOpenPeriod + 60); // forward time by marketOpenPeriod + 1 minute
 
@@ -131,15 +173,54 @@ describe("settleMarket", () => {
 
     const marketAcc = await fetchMarketAcc(program, marketPda);
 
+    expect(marketAcc.state).toEqual({ failed: {} });
+    expect(marketAcc.settledPrice).toBeCloseTo(191.14841996);
+  });
+
+  test("settle a market from a Switchboard pull feed", async () => {
+    const now = litesvm.getClock().unixTimestamp;
+
+    await program.methods
+      .createMarket({
+        resolveTs: new BN(Number(now) + marketOpenPeriod),
+        targetPrice: 150,
+        title: "Will SOL reach $150 in 24 hours?",
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        priceUpdateV2: SOL_USD_SWITCHBOARD_PULL_FEED,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tradingMint: USDC_MINT,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const marketPda = getMarketPda(1);
+
+    expect((await fetchMarketAcc(program, marketPda)).oracleSource).toEqual({
+      switchboard: {},
+    });
+
+    forwardTime(litesvm, marketOpenPeriod + 60); // forward time by marketOpenPeriod + 1 minute
+
+    await program.methods
+      .settleMarket()
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        market: marketPda,
+        priceUpdateV2: SOL_USD_SWITCHBOARD_PULL_FEED,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const marketAcc = await fetchMarketAcc(program, marketPda);
+
     expect(marketAcc.state).toEqual({ passed: {} });
+    expect(marketAcc.settledPrice).toBeCloseTo(191.14841996);
   });
 
   test("throws if market is already settled", async () => {
-    const now = litesvm.getClock().unixTimestamp;
-    const marketPda = getMarketPda(
-      SOL_USD_PRICE_UPDATE_V2,
-      Number(now) + marketOpenPeriod
-    );
+    const marketPda = getMarketPda(0);
 
     forwardTime(litesvm, marketOpenPeriod + 60); // forward time by marketOpenPeriod + 1 minute
 
@@ -171,11 +252,7 @@ describe("settleMarket", () => {
   });
 
   test("throws if market cannot be resolved yet", async () => {
-    const now = litesvm.getClock().unixTimestamp;
-    const marketPda = getMarketPda(
-      SOL_USD_PRICE_UPDATE_V2,
-      Number(now) + marketOpenPeriod
-    );
+    const marketPda = getMarketPda(0);
 
     forwardTime(litesvm, marketOpenPeriod - 1); // forward time by marketOpenPeriod - 1 second
 
@@ -193,4 +270,46 @@ describe("settleMarket", () => {
       expectAnchorError(err, "MarketCannotResolve");
     }
   });
+
+  test("throws if oracle price is stale", async () => {
+    const publishTime = 1753093731; // publish time of the SOL/USD price update fixture
+
+    forwardTime(
+      litesvm,
+      publishTime - Number(litesvm.getClock().unixTimestamp) - marketOpenPeriod
+    );
+
+    const now = litesvm.getClock().unixTimestamp;
+
+    await program.methods
+      .createMarket({
+        resolveTs: new BN(Number(now) + marketOpenPeriod + 60 * 2),
+        targetPrice: 150,
+        title: "Will SOL reach $150 in 24 hours?",
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tradingMint: USDC_MINT,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    forwardTime(litesvm, marketOpenPeriod + 60 * 2); // price update is now 2 minutes old
+
+    try {
+      await program.methods
+        .settleMarket()
+        .accountsPartial({
+          authority: configAuthority.publicKey,
+          market: getMarketPda(1),
+          priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        })
+        .signers([configAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "StalePrice");
+    }
+  });
 });
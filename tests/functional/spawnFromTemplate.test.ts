@@ -0,0 +1,237 @@
+import { BN, Program } from "@coral-xyz/anchor";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { beforeEach, describe, expect, test } from "bun:test";
+import { LiteSVM } from "litesvm";
+import { Prediction } from "../../target/types/prediction";
+import { Keypair } from "@solana/web3.js";
+import {
+  expectAnchorError,
+  forwardTime,
+  fundedSystemAccountInfo,
+  getSetup,
+} from "../setup";
+import { getMarketPda, getMarketTemplatePda } from "../pda";
+import { fetchMarketAcc, fetchMarketTemplateAcc } from "../accounts";
+import {
+  DISPUTE_WINDOW,
+  MAX_MARKET_DURATION,
+  MIN_MARKET_DURATION,
+  PROPOSAL_BOND,
+  SOL_USD_PRICE_UPDATE_V2,
+  USDC_MINT,
+} from "../constants";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+describe("spawnFromTemplate", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<Prediction>;
+  };
+
+  const [configAuthority, spawner] = Array.from({ length: 2 }, () =>
+    Keypair.generate()
+  );
+
+  const duration = 60 * 60; // 1 hour
+  const offsetBps = 100;
+  const marketTemplatePda = getMarketTemplatePda(0);
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      ...[configAuthority, spawner].map((kp) => {
+        return {
+          pubkey: kp.publicKey,
+          account: fundedSystemAccountInfo(),
+        };
+      }),
+    ]));
+
+    await program.methods
+      .initializeConfig({
+        feeBps: 10,
+        minMarketDuration: new BN(MIN_MARKET_DURATION),
+        maxMarketDuration: new BN(MAX_MARKET_DURATION),
+        proposalBond: new BN(PROPOSAL_BOND),
+        disputeWindow: new BN(DISPUTE_WINDOW),
+      })
+      .accounts({
+        authority: configAuthority.publicKey,
+        mint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    await program.methods
+      .createMarketTemplate({
+        condition: { above: {} },
+        duration: new BN(duration),
+        feeBps: 25,
+        strikeRule: { lastSettlementOffset: {} },
+        targetPrice: 150,
+        offsetBps,
+        title: "Will SOL close the hour above the strike?",
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+      })
+      .signers([configAuthority])
+      .rpc();
+  });
+
+  test("spawns the first market from template", async () => {
+    const now = litesvm.getClock().unixTimestamp;
+
+    await program.methods
+      .spawnFromTemplate()
+      .accountsPartial({
+        payer: spawner.publicKey,
+        marketTemplate: marketTemplatePda,
+        previousMarket: null,
+        market: getMarketPda(0),
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        tradingMint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([spawner])
+      .rpc();
+
+    const marketAcc = await fetchMarketAcc(program, getMarketPda(0));
+
+    expect(marketAcc.resolveTs.toNumber()).toBe(Number(now) + duration);
+    expect(marketAcc.targetPrice).toBe(150);
+    expect(marketAcc.template).toStrictEqual(marketTemplatePda);
+    expect(marketAcc.condition).toEqual({ above: {} });
+    expect(marketAcc.hasFeeOverride).toBe(true);
+    expect(marketAcc.feeBps).toBe(25);
+
+    const marketTemplateAcc = await fetchMarketTemplateAcc(
+      program,
+      marketTemplatePda
+    );
+
+    expect(marketTemplateAcc.lastMarket).toStrictEqual(getMarketPda(0));
+    expect(marketTemplateAcc.spawnCount.toNumber()).toBe(1);
+  });
+
+  test("rolls over to the next market after settlement", async () => {
+    await program.methods
+      .spawnFromTemplate()
+      .accountsPartial({
+        payer: spawner.publicKey,
+        marketTemplate: marketTemplatePda,
+        previousMarket: null,
+        market: getMarketPda(0),
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        tradingMint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([spawner])
+      .rpc();
+
+    forwardTime(litesvm, duration + 60); // forward time by duration + 1 minute
+
+    await program.methods
+      .settleMarket()
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        market: getMarketPda(0),
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const previousMarketAcc = await fetchMarketAcc(program, getMarketPda(0));
+
+    await program.methods
+      .spawnFromTemplate()
+      .accountsPartial({
+        payer: spawner.publicKey,
+        marketTemplate: marketTemplatePda,
+        previousMarket: getMarketPda(0),
+        market: getMarketPda(1),
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        tradingMint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([spawner])
+      .rpc();
+
+    const marketAcc = await fetchMarketAcc(program, getMarketPda(1));
+
+    expect(marketAcc.targetPrice).toBeCloseTo(
+      (previousMarketAcc.settledPrice * (10_000 + offsetBps)) / 10_000
+    );
+    expect(marketAcc.template).toStrictEqual(marketTemplatePda);
+  });
+
+  test("throws if previous market has not settled", async () => {
+    await program.methods
+      .spawnFromTemplate()
+      .accountsPartial({
+        payer: spawner.publicKey,
+        marketTemplate: marketTemplatePda,
+        previousMarket: null,
+        market: getMarketPda(0),
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        tradingMint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([spawner])
+      .rpc();
+
+    try {
+      await program.methods
+        .spawnFromTemplate()
+        .accountsPartial({
+          payer: spawner.publicKey,
+          marketTemplate: marketTemplatePda,
+          previousMarket: getMarketPda(0),
+          market: getMarketPda(1),
+          priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+          tradingMint: USDC_MINT,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([spawner])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "PreviousMarketNotSettled");
+    }
+  });
+
+  test("throws if previous market is omitted after the first spawn", async () => {
+    await program.methods
+      .spawnFromTemplate()
+      .accountsPartial({
+        payer: spawner.publicKey,
+        marketTemplate: marketTemplatePda,
+        previousMarket: null,
+        market: getMarketPda(0),
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        tradingMint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([spawner])
+      .rpc();
+
+    try {
+      await program.methods
+        .spawnFromTemplate()
+        .accountsPartial({
+          payer: spawner.publicKey,
+          marketTemplate: marketTemplatePda,
+          previousMarket: null,
+          market: getMarketPda(1),
+          priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+          tradingMint: USDC_MINT,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([spawner])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidPreviousMarket");
+    }
+  });
+});
@@ -0,0 +1,283 @@
+import { BN, Program } from "@coral-xyz/anchor";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { beforeEach, describe, expect, test } from "bun:test";
+import { LiteSVM } from "litesvm";
+import { Prediction } from "../../target/types/prediction";
+import { Keypair } from "@solana/web3.js";
+import {
+  expectAnchorError,
+  forwardTime,
+  fundedSystemAccountInfo,
+  getSetup,
+  initUsdcAta,
+} from "../setup";
+import { getMarketPda, getPositionPda } from "../pda";
+import { fetchMarketAcc, fetchPositionAcc } from "../accounts";
+import {
+  DISPUTE_WINDOW,
+  MAX_MARKET_DURATION,
+  MIN_MARKET_DURATION,
+  PROPOSAL_BOND,
+  SOL_USD_PRICE_UPDATE_V2,
+  USDC_MINT,
+  USDC_MINT_DECIMALS,
+} from "../constants";
+import {
+  getAccount,
+  getAssociatedTokenAddressSync,
+  MAX_FEE_BASIS_POINTS,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+
+describe("tradeOutcomeShares", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<Prediction>;
+  };
+
+  const [configAuthority, positionAuthority] = Array.from({ length: 2 }, () =>
+    Keypair.generate()
+  );
+
+  const initAtaBal = 100 * 10 ** USDC_MINT_DECIMALS;
+  const marketOpenPeriod = 60 * 60 * 24; // 1 day
+  const feeBps = 10;
+  const bounds = [120, 140, 160, 180];
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      ...[configAuthority, positionAuthority].map((kp) => {
+        return {
+          pubkey: kp.publicKey,
+          account: fundedSystemAccountInfo(),
+        };
+      }),
+    ]));
+
+    initUsdcAta(litesvm, positionAuthority.publicKey, initAtaBal);
+
+    await program.methods
+      .initializeConfig({
+        feeBps,
+        minMarketDuration: new BN(MIN_MARKET_DURATION),
+        maxMarketDuration: new BN(MAX_MARKET_DURATION),
+        proposalBond: new BN(PROPOSAL_BOND),
+        disputeWindow: new BN(DISPUTE_WINDOW),
+      })
+      .accounts({
+        authority: configAuthority.publicKey,
+        mint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const now = litesvm.getClock().unixTimestamp;
+
+    await program.methods
+      .createCategoricalMarket({
+        resolveTs: new BN(Number(now) + marketOpenPeriod),
+        bounds,
+        title: "Where will SOL close in 24 hours?",
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tradingMint: USDC_MINT,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    await program.methods
+      .openPosition()
+      .accountsPartial({
+        authority: positionAuthority.publicKey,
+        market: getMarketPda(0),
+      })
+      .signers([positionAuthority])
+      .rpc();
+  });
+
+  test("buy outcome shares", async () => {
+    const marketPda = getMarketPda(0);
+    const positionPda = getPositionPda(positionAuthority.publicKey, marketPda);
+
+    const sharesToBuy = 10;
+    const outcome = 2;
+
+    await program.methods
+      .tradeOutcomeShares({
+        shares: new BN(sharesToBuy),
+        outcome,
+        isBuy: true,
+      })
+      .accountsPartial({
+        authority: positionAuthority.publicKey,
+        configAuthority: configAuthority.publicKey,
+        market: marketPda,
+        position: positionPda,
+        tradingMint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([positionAuthority])
+      .rpc();
+
+    const positionAcc = await fetchPositionAcc(program, positionPda);
+    const marketAcc = await fetchMarketAcc(program, marketPda);
+
+    expect(positionAcc.outcomeShares[outcome].toNumber()).toBe(sharesToBuy);
+    expect(marketAcc.outcomeShares[outcome].toNumber()).toBe(sharesToBuy);
+
+    const price = sharesToBuy * 10 ** USDC_MINT_DECIMALS;
+    const fee = price * (feeBps / MAX_FEE_BASIS_POINTS);
+
+    expect(positionAcc.outcomeCostBasis[outcome].toNumber()).toBe(price);
+    expect(positionAcc.totalFeesPaid.toNumber()).toBe(fee);
+
+    const marketAta = getAssociatedTokenAddressSync(
+      USDC_MINT,
+      marketPda,
+      true,
+      TOKEN_PROGRAM_ID
+    );
+    const marketAtaAcc = await getAccount(
+      provider.connection,
+      marketAta,
+      "processed"
+    );
+
+    expect(Number(marketAtaAcc.amount)).toBe(price - fee);
+  });
+
+  test("throws if outcome does not exist", async () => {
+    const marketPda = getMarketPda(0);
+    const positionPda = getPositionPda(positionAuthority.publicKey, marketPda);
+
+    try {
+      await program.methods
+        .tradeOutcomeShares({
+          shares: new BN(10),
+          outcome: bounds.length + 1,
+          isBuy: true,
+        })
+        .accountsPartial({
+          authority: positionAuthority.publicKey,
+          configAuthority: configAuthority.publicKey,
+          market: marketPda,
+          position: positionPda,
+          tradingMint: USDC_MINT,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([positionAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidOutcome");
+    }
+  });
+
+  test("throws if binary shares are traded in a categorical market", async () => {
+    const marketPda = getMarketPda(0);
+    const positionPda = getPositionPda(positionAuthority.publicKey, marketPda);
+
+    try {
+      await program.methods
+        .tradeShares({
+          shares: new BN(10),
+          isBuy: true,
+          isPass: true,
+        })
+        .accountsPartial({
+          authority: positionAuthority.publicKey,
+          configAuthority: configAuthority.publicKey,
+          market: marketPda,
+          position: positionPda,
+          tradingMint: USDC_MINT,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([positionAuthority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidMarketKind");
+    }
+  });
+
+  test("settles to the bucket containing the price and pays the winning outcome", async () => {
+    const marketPda = getMarketPda(0);
+    const positionPda = getPositionPda(positionAuthority.publicKey, marketPda);
+
+    for (let outcome = 0; outcome <= bounds.length; outcome++) {
+      await program.methods
+        .tradeOutcomeShares({
+          shares: new BN(1),
+          outcome,
+          isBuy: true,
+        })
+        .accountsPartial({
+          authority: positionAuthority.publicKey,
+          configAuthority: configAuthority.publicKey,
+          market: marketPda,
+          position: positionPda,
+          tradingMint: USDC_MINT,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([positionAuthority])
+        .rpc();
+    }
+
+    forwardTime(litesvm, marketOpenPeriod + 60); // forward time by marketOpenPeriod + 1 minute
+
+    await program.methods
+      .settleMarket()
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        market: marketPda,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const marketAcc = await fetchMarketAcc(program, marketPda);
+    const expectedOutcome = bounds.filter(
+      (bound) => marketAcc.settledPrice >= bound
+    ).length;
+
+    expect(marketAcc.state).toEqual({ resolved: {} });
+    expect(marketAcc.winningOutcome).toBe(expectedOutcome);
+
+    const marketAta = getAssociatedTokenAddressSync(
+      USDC_MINT,
+      marketPda,
+      true,
+      TOKEN_PROGRAM_ID
+    );
+    const preMarketAtaAcc = await getAccount(
+      provider.connection,
+      marketAta,
+      "processed"
+    );
+
+    await program.methods
+      .claimWinnings()
+      .accountsPartial({
+        authority: positionAuthority.publicKey,
+        market: marketPda,
+        position: positionPda,
+        tradingMint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([positionAuthority])
+      .rpc();
+
+    const postMarketAtaAcc = await getAccount(
+      provider.connection,
+      marketAta,
+      "processed"
+    );
+
+    // sole holder of the winning outcome takes the whole pot
+    expect(Number(postMarketAtaAcc.amount)).toBe(0);
+    expect(Number(preMarketAtaAcc.amount)).toBeGreaterThan(0);
+  });
+});
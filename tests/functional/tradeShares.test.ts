@@ -11,8 +11,16 @@ import {
   initUsdcAta,
 } from "../setup";
 import { getConfigPda, getMarketPda, getPositionPda } from "../pda";
-import { fetchConfigAcc, fetchPositionAcc } from "../accounts";
 import {
+  fetchConfigAcc,
+  fetchMarketAcc,
+  fetchPositionAcc,
+} from "../accounts";
+import {
+  DISPUTE_WINDOW,
+  MAX_MARKET_DURATION,
+  MIN_MARKET_DURATION,
+  PROPOSAL_BOND,
   SOL_USD_PRICE_UPDATE_V2,
   USDC_MINT,
   USDC_MINT_DECIMALS,
@@ -54,6 +62,10 @@ describe("tradeShares", () => {
     await program.methods
       .initializeConfig({
         feeBps: 10,
+        minMarketDuration: new BN(MIN_MARKET_DURATION),
+        maxMarketDuration: new BN(MAX_MARKET_DURATION),
+        proposalBond: new BN(PROPOSAL_BOND),
+        disputeWindow: new BN(DISPUTE_WINDOW),
       })
       .accounts({
         authority: configAuthority.publicKey,
@@ -81,7 +93,7 @@ describe("tradeShares", () => {
       .signers([configAuthority])
       .rpc();
 
-    const marketPda = getMarketPda(SOL_USD_PRICE_UPDATE_V2, resolveTs);
+    const marketPda = getMarketPda(0);
 
     await program.methods
       .openPosition()
@@ -94,11 +106,7 @@ describe("tradeShares", () => {
   });
 
   test("buy shares", async () => {
-    const now = litesvm.getClock().unixTimestamp;
-    const marketPda = getMarketPda(
-      SOL_USD_PRICE_UPDATE_V2,
-      Number(now) + marketOpenPeriod
-    );
+    const marketPda = getMarketPda(0);
     const positionPda = getPositionPda(positionAuthority.publicKey, marketPda);
 
     const sharesToBuy = 10;
@@ -141,6 +149,17 @@ describe("tradeShares", () => {
     const fee = price * (feeBps / MAX_FEE_BASIS_POINTS);
 
     expect(Number(marketAtaAcc.amount)).toBe(price - fee);
+    expect(positionAcc.passCostBasis.toNumber()).toBe(price);
+    expect(positionAcc.totalFeesPaid.toNumber()).toBe(fee);
+
+    const marketAcc = await fetchMarketAcc(program, marketPda);
+
+    expect(marketAcc.volume.toNumber()).toBe(price);
+    expect(marketAcc.feesCollected.toNumber()).toBe(fee);
+    expect(marketAcc.tradeCount.toNumber()).toBe(1);
+    expect(marketAcc.lastTradeTs.toNumber()).toBe(
+      Number(litesvm.getClock().unixTimestamp)
+    );
 
     const configAta = getAssociatedTokenAddressSync(
       USDC_MINT,
@@ -173,11 +192,7 @@ describe("tradeShares", () => {
   });
 
   test("sell shares", async () => {
-    const now = litesvm.getClock().unixTimestamp;
-    const marketPda = getMarketPda(
-      SOL_USD_PRICE_UPDATE_V2,
-      Number(now) + marketOpenPeriod
-    );
+    const marketPda = getMarketPda(0);
     const positionPda = getPositionPda(positionAuthority.publicKey, marketPda);
 
     const sharesToBuy = 10;
@@ -264,15 +279,34 @@ describe("tradeShares", () => {
       marketAta,
       "processed"
     );
-    const price = sharesToSell * 10 ** USDC_MINT_DECIMALS;
 
     const configAcc = await fetchConfigAcc(program, configPda);
     const feeBps = configAcc.feeBps;
-    const fee = price * (feeBps / MAX_FEE_BASIS_POINTS);
+    const buyPrice = sharesToBuy * 10 ** USDC_MINT_DECIMALS;
+    const buyFee = Math.floor((buyPrice * feeBps) / MAX_FEE_BASIS_POINTS);
+    // sales are capped at the shares' pro-rata claim on the collateral backing them
+    const price = ((buyPrice - buyFee) * sharesToSell) / sharesToBuy;
+    const fee = Math.floor((price * feeBps) / MAX_FEE_BASIS_POINTS);
+    const releasedCostBasis = (buyPrice * sharesToSell) / sharesToBuy;
 
     expect(Number(preMarketAtaAcc.amount)).toBe(
       Number(postMarketAtaAcc.amount) + price
     );
+    expect(positionAcc.passCostBasis.toNumber()).toBe(
+      buyPrice - releasedCostBasis
+    );
+    expect(positionAcc.realizedPnl.toNumber()).toBe(
+      price - fee - releasedCostBasis
+    );
+
+    const marketAcc = await fetchMarketAcc(program, marketPda);
+
+    expect(marketAcc.totalCollateral.toNumber()).toBe(
+      Number(postMarketAtaAcc.amount)
+    );
+    expect(marketAcc.outcomeCollateral[0].toNumber()).toBe(
+      buyPrice - buyFee - price
+    );
 
     const postConfigAtaAcc = await getAccount(
       provider.connection,
@@ -297,11 +331,7 @@ describe("tradeShares", () => {
   });
 
   test("throws if there's not enough shares to sell", async () => {
-    const now = litesvm.getClock().unixTimestamp;
-    const marketPda = getMarketPda(
-      SOL_USD_PRICE_UPDATE_V2,
-      Number(now) + marketOpenPeriod
-    );
+    const marketPda = getMarketPda(0);
     const positionPda = getPositionPda(positionAuthority.publicKey, marketPda);
     const positionAcc = await fetchPositionAcc(program, positionPda);
 
@@ -0,0 +1,117 @@
+import { BN, Program } from "@coral-xyz/anchor";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { beforeEach, describe, expect, test } from "bun:test";
+import { LiteSVM } from "litesvm";
+import { Prediction } from "../../target/types/prediction";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { fundedSystemAccountInfo, getSetup } from "../setup";
+import { getMarketMetadataPda, getMarketPda } from "../pda";
+import { fetchMarketMetadataAcc } from "../accounts";
+import {
+  DISPUTE_WINDOW,
+  MAX_MARKET_DURATION,
+  MIN_MARKET_DURATION,
+  PROPOSAL_BOND,
+  SOL_USD_PRICE_UPDATE_V2,
+  USDC_MINT,
+} from "../constants";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+describe("updateMarketMetadata", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<Prediction>;
+  };
+
+  const configAuthority = Keypair.generate();
+
+  const marketOpenPeriod = 60 * 60 * 24; // 1 day
+  let marketPda: PublicKey;
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      {
+        pubkey: configAuthority.publicKey,
+        account: fundedSystemAccountInfo(),
+      },
+    ]));
+
+    await program.methods
+      .initializeConfig({
+        feeBps: 10,
+        minMarketDuration: new BN(MIN_MARKET_DURATION),
+        maxMarketDuration: new BN(MAX_MARKET_DURATION),
+        proposalBond: new BN(PROPOSAL_BOND),
+        disputeWindow: new BN(DISPUTE_WINDOW),
+      })
+      .accounts({
+        authority: configAuthority.publicKey,
+        mint: USDC_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const now = litesvm.getClock().unixTimestamp;
+    const resolveTs = Number(now) + marketOpenPeriod;
+
+    await program.methods
+      .createMarket({
+        resolveTs: new BN(resolveTs),
+        targetPrice: 150,
+        title: "Will SOL reach $150 in 24 hours?",
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        priceUpdateV2: SOL_USD_PRICE_UPDATE_V2,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tradingMint: USDC_MINT,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    marketPda = getMarketPda(0);
+
+    await program.methods
+      .createMarketMetadata({
+        description: "",
+        category: { crypto: {} },
+        tags: [],
+        uri: "",
+      })
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        market: marketPda,
+      })
+      .signers([configAuthority])
+      .rpc();
+  });
+
+  test("updates market metadata", async () => {
+    const metadata = {
+      description: "Resolves PASS if the Pyth SOL/USD price is at least $150.",
+      category: { crypto: {} },
+      tags: ["sol", "daily"],
+      uri: "https://example.com/markets/sol-150.json",
+    };
+
+    await program.methods
+      .updateMarketMetadata(metadata)
+      .accountsPartial({
+        authority: configAuthority.publicKey,
+        market: marketPda,
+      })
+      .signers([configAuthority])
+      .rpc();
+
+    const marketMetadataAcc = await fetchMarketMetadataAcc(
+      program,
+      getMarketMetadataPda(marketPda)
+    );
+
+    expect(marketMetadataAcc.description).toBe(metadata.description);
+    expect(marketMetadataAcc.tags).toEqual(metadata.tags);
+    expect(marketMetadataAcc.uri).toBe(metadata.uri);
+  });
+});
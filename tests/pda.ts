@@ -1,9 +1,9 @@
 import { PublicKey } from "@solana/web3.js";
 import { PREDICTION_PROGRAM_ID } from "./constants";
 
-function getInt64Buffer(value: number) {
+function getUint64Buffer(value: number) {
   const buffer = Buffer.alloc(8);
-  buffer.writeBigInt64LE(BigInt(value), 0);
+  buffer.writeBigUInt64LE(BigInt(value), 0);
   return buffer;
 }
 
@@ -14,13 +14,23 @@ export function getConfigPda() {
   )[0];
 }
 
-export function getMarketPda(priceUpdateV2: PublicKey, resolveTs: number) {
+export function getMarketPda(id: number) {
   return PublicKey.findProgramAddressSync(
-    [
-      Buffer.from("market"),
-      priceUpdateV2.toBuffer(),
-      getInt64Buffer(resolveTs),
-    ],
+    [Buffer.from("market"), getUint64Buffer(id)],
+    PREDICTION_PROGRAM_ID
+  )[0];
+}
+
+export function getMarketMetadataPda(marketPda: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("market_metadata"), marketPda.toBuffer()],
+    PREDICTION_PROGRAM_ID
+  )[0];
+}
+
+export function getMarketSeriesPda(id: number) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("market_series"), getUint64Buffer(id)],
     PREDICTION_PROGRAM_ID
   )[0];
 }
@@ -31,3 +41,24 @@ export function getPositionPda(authority: PublicKey, marketPda: PublicKey) {
     PREDICTION_PROGRAM_ID
   )[0];
 }
+
+export function getMarketTemplatePda(id: number) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("market_template"), getUint64Buffer(id)],
+    PREDICTION_PROGRAM_ID
+  )[0];
+}
+
+export function getResolutionPda(marketPda: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("resolution"), marketPda.toBuffer()],
+    PREDICTION_PROGRAM_ID
+  )[0];
+}
+
+export function getPriceSamplesPda(marketPda: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("price_samples"), marketPda.toBuffer()],
+    PREDICTION_PROGRAM_ID
+  )[0];
+}
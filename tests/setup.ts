@@ -8,18 +8,26 @@ import { expect } from "bun:test";
 import {
   ACCOUNT_SIZE,
   AccountLayout,
+  AccountType,
+  ExtensionType,
   getAssociatedTokenAddressSync,
+  getMintLen,
+  getTypeLen,
   MINT_SIZE,
   MintLayout,
+  TOKEN_2022_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
   PYTH_SOLANA_RECEIVER_PROGRAM_ID,
   SOL_USD_PRICE_UPDATE_V2,
+  SOL_USD_SWITCHBOARD_PULL_FEED,
+  SWITCHBOARD_ON_DEMAND_PROGRAM_ID,
   USDC_MINT,
   USDC_MINT_DECIMALS,
 } from "./constants";
 import priceUpdateV2AccInfo from "./fixtures/sol_usd_price_update_v2.json";
+import pullFeedAccInfo from "./fixtures/sol_usd_switchboard_pull_feed.json";
 
 export async function getSetup(
   accounts: { pubkey: PublicKey; account: AccountInfoBytes }[] = []
@@ -36,6 +44,13 @@ export async function getSetup(
     owner: PYTH_SOLANA_RECEIVER_PROGRAM_ID,
   });
 
+  litesvm.setAccount(SOL_USD_SWITCHBOARD_PULL_FEED, {
+    data: Buffer.from(pullFeedAccInfo.account.data[0], "base64"),
+    executable: false,
+    lamports: LAMPORTS_PER_SOL,
+    owner: SWITCHBOARD_ON_DEMAND_PROGRAM_ID,
+  });
+
   for (const { pubkey, account } of accounts) {
     litesvm.setAccount(new PublicKey(pubkey), {
       data: account.data,
@@ -105,6 +120,47 @@ function initUsdcMint(litesvm: LiteSVM) {
   });
 }
 
+export function initToken2022Mint(
+  litesvm: LiteSVM,
+  mint: PublicKey,
+  decimals: number,
+  extensions: ExtensionType[]
+) {
+  const mintData = Buffer.alloc(getMintLen(extensions));
+
+  MintLayout.encode(
+    {
+      mintAuthority: PublicKey.default,
+      mintAuthorityOption: 0,
+      supply: BigInt(1000 * 10 ** decimals),
+      decimals,
+      isInitialized: true,
+      freezeAuthority: PublicKey.default,
+      freezeAuthorityOption: 0,
+    },
+    mintData
+  );
+
+  // account type follows the base account length, then TLV-encoded extensions
+  let offset = ACCOUNT_SIZE;
+  mintData.writeUInt8(AccountType.Mint, offset);
+  offset += 1;
+
+  for (const extension of extensions) {
+    const len = getTypeLen(extension);
+    mintData.writeUInt16LE(extension, offset);
+    mintData.writeUInt16LE(len, offset + 2);
+    offset += 4 + len;
+  }
+
+  litesvm.setAccount(mint, {
+    data: mintData,
+    executable: false,
+    lamports: LAMPORTS_PER_SOL,
+    owner: TOKEN_2022_PROGRAM_ID,
+  });
+}
+
 export function initUsdcAta(
   litesvm: LiteSVM,
   owner: PublicKey,
@@ -132,7 +188,7 @@ export function initUsdcAta(
   const ata = getAssociatedTokenAddressSync(
     USDC_MINT,
     owner,
-    false,
+    true,
     TOKEN_PROGRAM_ID
   );
 